@@ -0,0 +1,253 @@
+//! Minimal unsigned big integer for exact comparisons of decimals with binary values,
+//! and exact decimal expansion of binary values
+
+use std::cmp::Ordering;
+
+// Unsigned integer, 32 bit limbs with least significant first, no trailing zero limbs
+#[derive(Clone, Debug)]
+pub(crate) struct BigInt(Vec<u32>);
+
+impl BigInt {
+    pub(crate) fn from_u64(value: u64) -> Self {
+        let mut result = BigInt(vec![value as u32, (value >> 32) as u32]);
+        result.trim();
+        result
+    }
+
+    // Digits in range 0..=9, most significant first
+    pub(crate) fn from_digits(digits: &[u8]) -> Self {
+        const CHUNK_DIGITS: usize = 9;
+        const POWER_OF_10: [u32; CHUNK_DIGITS + 1] = [
+            1, 10, 100, 1000, 10000, 100000, 1000000, 10000000, 100000000, 1000000000
+        ];
+
+        let mut result = BigInt(Vec::with_capacity(digits.len() / CHUNK_DIGITS + 1));
+        for chunk in digits.chunks(CHUNK_DIGITS) {
+            let value = chunk.iter().fold(0u32, |value, &digit| value * 10 + digit as u32);
+            result.mul_add_small(POWER_OF_10[chunk.len()], value);
+        }
+        result
+    }
+
+    // Self = Self * Multiplier + Addend
+    pub(crate) fn mul_add_small(&mut self, multiplier: u32, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in self.0.iter_mut() {
+            let value = *limb as u64 * multiplier as u64 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry != 0 {
+            self.0.push(carry as u32);
+        }
+        self.trim();
+    }
+
+    pub(crate) fn mul_pow5(&mut self, mut exponent: u32) {
+        const LAST_POWER_5_EXPONENT: u32 = 13;
+        const LAST_POWER_5: u32 = 1220703125; // 5^13, largest in u32
+
+        while exponent >= LAST_POWER_5_EXPONENT {
+            self.mul_add_small(LAST_POWER_5, 0);
+            exponent -= LAST_POWER_5_EXPONENT;
+        }
+        self.mul_add_small(5u32.pow(exponent), 0);
+    }
+
+    pub(crate) fn mul_pow2(&mut self, exponent: u32) {
+        if self.0.is_empty() {
+            return;
+        }
+        let limbs = (exponent / 32) as usize;
+        let bits = exponent % 32;
+        if bits != 0 {
+            let mut carry = 0;
+            for limb in self.0.iter_mut() {
+                let value = *limb;
+                *limb = (value << bits) | carry;
+                carry = value >> (32 - bits);
+            }
+            if carry != 0 {
+                self.0.push(carry);
+            }
+        }
+        self.0.splice(0..0, std::iter::repeat_n(0, limbs));
+    }
+
+    // Self = Self + Other
+    pub(crate) fn add(&mut self, other: &BigInt) {
+        if self.0.len() < other.0.len() {
+            self.0.resize(other.0.len(), 0);
+        }
+        let mut carry = 0u64;
+        for (i, limb) in self.0.iter_mut().enumerate() {
+            let value = *limb as u64 + other.0.get(i).copied().unwrap_or(0) as u64 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry != 0 {
+            self.0.push(carry as u32);
+        }
+    }
+
+    // Self = Self - Other, condition: Self >= Other
+    pub(crate) fn sub(&mut self, other: &BigInt) {
+        let mut borrow = 0i64;
+        for (i, limb) in self.0.iter_mut().enumerate() {
+            let value = *limb as i64 - other.0.get(i).copied().unwrap_or(0) as i64 - borrow;
+            *limb = value as u32;
+            borrow = (value < 0) as i64;
+        }
+        self.trim();
+    }
+
+    // Self = Self / Divisor, returns remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for limb in self.0.iter_mut().rev() {
+            let value = (remainder << 32) | *limb as u64;
+            *limb = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+        self.trim();
+        remainder as u32
+    }
+
+    // Decimal digits in range 0..=9, most significant first, empty for zero
+    pub(crate) fn to_digits(&self) -> Vec<u8> {
+        const CHUNK_DIGITS: usize = 9;
+        const CHUNK: u32 = 1000000000; // 10^9
+
+        let mut value = self.clone();
+        let mut digits = Vec::with_capacity(self.0.len() * 10);
+        while !value.is_zero() {
+            let mut chunk = value.div_rem_small(CHUNK);
+            for _ in 0..CHUNK_DIGITS {
+                digits.push((chunk % 10) as u8);
+                chunk /= 10;
+            }
+        }
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        digits.reverse();
+        digits
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn trim(&mut self) {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+}
+
+impl PartialEq for BigInt {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for BigInt {}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.len().cmp(&other.0.len())
+            .then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+    }
+}
+
+// Exact decimal digits of positive finite Value, with exponent of the first digit
+pub(crate) fn exact_digits(value: f64) -> (Vec<u8>, isize) {
+    let bits = value.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7FF) as isize;
+    let mantissa = bits & ((1 << 52) - 1);
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        (mantissa, -1074)
+    } else {
+        (mantissa | (1 << 52), biased_exponent - 1075)
+    };
+
+    // Mantissa * 2^Exponent = Mantissa * 5^-Exponent * 10^Exponent
+    let mut number = BigInt::from_u64(mantissa);
+    let mut exponent_10 = 0;
+    if exponent >= 0 {
+        number.mul_pow2(exponent as u32);
+    } else {
+        number.mul_pow5(-exponent as u32);
+        exponent_10 = exponent;
+    }
+    let digits = number.to_digits();
+    let exponent_10 = exponent_10 + digits.len() as isize - 1;
+    (digits, exponent_10)
+}
+
+// Integer nearest to Digits with Count first digits before point, ties to even,
+// has Count + 1 digits after carry, empty for zero
+pub(crate) fn round_digits(digits: &[u8], count: isize) -> Vec<u8> {
+    if count < 0 {
+        return Vec::new();
+    }
+    let count = count as usize;
+
+    let mut result: Vec<u8> = (0..count).map(|i| digits.get(i).copied().unwrap_or(0)).collect();
+    let next = digits.get(count).copied().unwrap_or(0);
+    let is_odd = result.last().is_some_and(|digit| digit % 2 == 1);
+    let has_rest = digits.iter().skip(count + 1).any(|&digit| digit != 0);
+    if next > 5 || next == 5 && (has_rest || is_odd) {
+        // carry
+        let mut i = count;
+        while i > 0 && result[i - 1] == 9 {
+            result[i - 1] = 0;
+            i -= 1;
+        }
+        if i == 0 {
+            result.insert(0, 1);
+        } else {
+            result[i - 1] += 1;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BigInt;
+
+    #[test]
+    fn arithmetic() {
+        let mut value = BigInt::from_digits(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 1, 2]);
+        assert_eq!(value, BigInt::from_u64(123456789012));
+        value.mul_pow5(30);
+        value.mul_pow2(70);
+        let mut expected = BigInt::from_u64(123456789012);
+        for _ in 0..30 {
+            expected.mul_add_small(10, 0);
+        }
+        expected.mul_pow2(40);
+        assert_eq!(value, expected);
+        assert!(BigInt::from_u64(1 << 40) > BigInt::from_u64((1 << 40) - 1));
+        assert!(BigInt::from_u64(0).is_zero());
+        assert_eq!(BigInt::from_u64(1000000000123).to_digits(), [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3]);
+        assert_eq!(BigInt::from_u64(0).to_digits(), []);
+
+        let mut sum = BigInt::from_u64(u64::MAX);
+        sum.add(&BigInt::from_u64(u64::MAX));
+        let mut expected = BigInt::from_u64(u64::MAX);
+        expected.mul_pow2(1);
+        assert_eq!(sum, expected);
+        sum.sub(&BigInt::from_u64(u64::MAX));
+        assert_eq!(sum, BigInt::from_u64(u64::MAX));
+        sum.sub(&BigInt::from_u64(u64::MAX));
+        assert!(sum.is_zero());
+    }
+}
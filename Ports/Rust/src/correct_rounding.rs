@@ -0,0 +1,277 @@
+//! Exact rounding fallback, compares the decimal digits with midpoints between binary values
+//! using big integers. Used for halfway cases with the `correct-rounding` feature, for
+//! directed rounding and for range errors of `pure_strtod`.
+
+use std::cmp::Ordering;
+
+use crate::bigint::BigInt;
+#[cfg(feature = "correct-rounding")]
+use crate::double_double::DoubleDouble;
+use crate::fixed_decimal::FixedDecimal;
+
+// Positive binary float, with value Mantissa * 2^Exponent
+#[cfg_attr(not(feature = "correct-rounding"), allow(dead_code))]
+pub(crate) trait BinaryFloat: Copy + PartialEq {
+    const ZERO: Self;
+
+    fn next_up(self) -> Self;
+
+    fn next_down(self) -> Self;
+
+    fn is_infinite(self) -> bool;
+
+    fn is_even(self) -> bool;
+
+    // infinity is the power of two after the max value
+    fn parts(self) -> (u64, i32);
+}
+
+impl BinaryFloat for f64 {
+    const ZERO: Self = 0.0;
+
+    fn next_up(self) -> Self {
+        f64::next_up(self)
+    }
+
+    fn next_down(self) -> Self {
+        f64::next_down(self)
+    }
+
+    fn is_infinite(self) -> bool {
+        f64::is_infinite(self)
+    }
+
+    fn is_even(self) -> bool {
+        self.to_bits() & 1 == 0
+    }
+
+    fn parts(self) -> (u64, i32) {
+        if self.is_infinite() {
+            return (1 << 53, 971);
+        }
+        let bits = self.to_bits();
+        let exponent = ((bits >> 52) & 0x7FF) as i32;
+        let mantissa = bits & ((1 << 52) - 1);
+        if exponent == 0 {
+            (mantissa, -1074)
+        } else {
+            (mantissa | (1 << 52), exponent - 1075)
+        }
+    }
+}
+
+impl BinaryFloat for f32 {
+    const ZERO: Self = 0.0;
+
+    fn next_up(self) -> Self {
+        f32::next_up(self)
+    }
+
+    fn next_down(self) -> Self {
+        f32::next_down(self)
+    }
+
+    fn is_infinite(self) -> bool {
+        f32::is_infinite(self)
+    }
+
+    fn is_even(self) -> bool {
+        self.to_bits() & 1 == 0
+    }
+
+    fn parts(self) -> (u64, i32) {
+        if self.is_infinite() {
+            return (1 << 24, 104);
+        }
+        let bits = self.to_bits();
+        let exponent = ((bits >> 23) & 0xFF) as i32;
+        let mantissa = (bits & ((1 << 23) - 1)) as u64;
+        if exponent == 0 {
+            (mantissa, -149)
+        } else {
+            (mantissa | (1 << 23), exponent - 150)
+        }
+    }
+}
+
+// Relative distance to the midpoint, below which Double-Double result can be on the wrong side
+pub(crate) const HALFWAY_TOLERANCE: f64 = 1.0 / 1048576.0; // 2^-20 ulp
+// Below 2^-968 Lo loses precision because of denormals
+pub(crate) const MIN_ACCURATE_DOUBLE: f64 = 4.008336720017946e-292; // 2^-968
+
+impl FixedDecimal {
+    // Compare absolute value of decimal with Mantissa * 2^Exponent exactly
+    pub(crate) fn compare(&self, mantissa: u64, exponent: i32) -> Ordering {
+        let exponent_10 = (self.exponent() - self.digits().len() as isize + 1) as i32;
+
+        let mut left = BigInt::from_digits(self.digits());
+        let mut right = BigInt::from_u64(mantissa);
+        let mut left_exponent_2 = 0;
+        let mut right_exponent_2 = exponent;
+        if exponent_10 >= 0 {
+            left.mul_pow5(exponent_10 as u32);
+            left_exponent_2 += exponent_10;
+        } else {
+            right.mul_pow5(-exponent_10 as u32);
+            right_exponent_2 -= exponent_10;
+        }
+        if !left.is_zero() && !right.is_zero() {
+            if left_exponent_2 > right_exponent_2 {
+                left.mul_pow2((left_exponent_2 - right_exponent_2) as u32);
+            } else {
+                right.mul_pow2((right_exponent_2 - left_exponent_2) as u32);
+            }
+        }
+
+        // dropped digits make decimal a bit larger
+        match left.cmp(&right) {
+        Ordering::Equal if self.is_truncated() => Ordering::Greater,
+        ordering => ordering,
+        }
+    }
+
+    // Absolute value is tiny after rounding like in IEEE 754: rounded to 53 bits with unbounded
+    // exponent it is below the min normal Double, so values from 2^-1022 - 2^-1076 up are not
+    // tiny. Value is the Double nearest to decimal. Same rule as SSE and glibc strtod on x86-64
+    pub(crate) fn is_tiny(&self, value: f64) -> bool {
+        let abs = value.abs();
+        if self.digits().is_empty() || abs > f64::MIN_POSITIVE {
+            return false;
+        }
+        abs < f64::MIN_POSITIVE || self.compare((1 << 54) - 1, -1076) == Ordering::Less
+    }
+
+    // Compare absolute value of decimal with the midpoint between Down and Up
+    pub(crate) fn compare_midpoint<T: BinaryFloat>(&self, down: T, up: T) -> Ordering {
+        let (down_mantissa, down_exponent) = down.parts();
+        let (up_mantissa, up_exponent) = up.parts();
+        let exponent = down_exponent.min(up_exponent);
+        let sum = (down_mantissa << (down_exponent - exponent)) + (up_mantissa << (up_exponent - exponent));
+        self.compare(sum, exponent - 1)
+    }
+
+    // Find nearest to decimal value, starting from the approximate Candidate, ties to even
+    #[cfg(feature = "correct-rounding")]
+    pub(crate) fn round_exact<T: BinaryFloat>(&self, candidate: T) -> T {
+        let mut value = candidate;
+        let mut moved_up = false;
+        while !value.is_infinite() {
+            let up = value.next_up();
+            match self.compare_midpoint(value, up) {
+            Ordering::Greater => { value = up; moved_up = true; }
+            Ordering::Equal => return if value.is_even() { value } else { up },
+            Ordering::Less => break,
+            }
+        }
+        while !moved_up && value != T::ZERO {
+            let down = value.next_down();
+            match self.compare_midpoint(down, value) {
+            Ordering::Less => value = down,
+            Ordering::Equal => return if value.is_even() { value } else { down },
+            Ordering::Greater => break,
+            }
+        }
+        value
+    }
+}
+
+// Double-Double result is too close to the midpoint to the next Double, or out of accurate range
+#[cfg(feature = "correct-rounding")]
+pub(crate) fn is_near_halfway(number: DoubleDouble) -> bool {
+    if !(MIN_ACCURATE_DOUBLE..f64::MAX).contains(&number.hi) {
+        return true;
+    }
+    let ulp = if number.lo > 0.0 { number.hi.next_up() - number.hi } else { number.hi - number.hi.next_down() };
+    (number.lo.abs() - ulp / 2.0).abs() <= ulp * HALFWAY_TOLERANCE
+}
+
+// Double-Double result is too close to the midpoint between Single and its neighbour
+#[cfg(feature = "correct-rounding")]
+pub(crate) fn is_near_halfway_f32(number: DoubleDouble, single: f32) -> bool {
+    const SINGLE_OVERFLOW: f64 = 340282366920938463463374607431768211456.0; // 2^128
+    let wide = |single: f32| if single.is_infinite() { SINGLE_OVERFLOW } else { single as f64 };
+
+    let single_hi = wide(single);
+    let other = if number.hi > single_hi || number.hi == single_hi && number.lo > 0.0 {
+        single.next_up()
+    } else {
+        single.next_down()
+    };
+    let other_hi = wide(other);
+    let midpoint = (single_hi + other_hi) / 2.0;
+    ((number.hi - midpoint) + number.lo).abs() <= (other_hi - single_hi).abs() * HALFWAY_TOLERANCE
+}
+
+#[cfg(all(test, feature = "correct-rounding"))]
+mod tests {
+    use crate::{parse_f32, parse_float};
+    use crate::random::random_generator;
+
+    // Exact decimal of the midpoint between Value and the next Double up, in fixed notation
+    fn midpoint_text(value: f64) -> String {
+        const DECIMALS: usize = 1100;
+        let half_ulp = (value.next_up() - value) / 2.0;
+        let a = format!("{:.*}", DECIMALS, value).into_bytes();
+        let b = format!("{:.*}", DECIMALS, half_ulp).into_bytes();
+        let b = [vec![b'0'; a.len() - b.len()], b].concat();
+        let mut sum = vec![b'0'; a.len()];
+        let mut carry = 0;
+        for i in (0..a.len()).rev() {
+            if a[i] == b'.' {
+                sum[i] = b'.';
+                continue;
+            }
+            let digit = (a[i] - b'0') + (b[i] - b'0') + carry;
+            sum[i] = b'0' + digit % 10;
+            carry = digit / 10;
+        }
+        let text = String::from_utf8(sum).unwrap();
+        if carry != 0 { format!("1{text}") } else { text }
+    }
+
+    #[test]
+    fn halfway_cases() {
+        let mut next = random_generator(0xD1B54A32D192ED03);
+        for i in 0..3000 {
+            let bits = match i % 3 {
+            0 => next() % 0x7FEF_FFFF_FFFF_FFFF,
+            1 => next() % 0x0020_0000_0000_0000, // denormals and near them
+            _ => (next() % 0x7FE) << 52, // powers of two
+            };
+            let value = f64::from_bits(bits);
+            let midpoint = midpoint_text(value);
+            for text in [midpoint.clone(), format!("{midpoint}0000001")] {
+                let expected: f64 = text.parse().unwrap();
+                let (result, count) = parse_float(text.as_bytes()).unwrap();
+                assert_eq!(count, text.len());
+                assert_eq!(result.to_bits(), expected.to_bits(), "{text}");
+            }
+        }
+    }
+
+    #[test]
+    fn long_mantissas() {
+        let mut next = random_generator(0x9E3779B97F4A7C15);
+        for _ in 0..100_000 {
+            let digits = 17 + (next() % 60) as usize;
+            let mut text: String = (0..digits).map(|_| (b'0' + (next() % 10) as u8) as char).collect();
+            let exponent = (next() % 700) as i64 - 380;
+            text.push_str(&format!("e{exponent}"));
+            let expected: f64 = text.parse().unwrap();
+            assert_eq!(parse_float(text.as_bytes()).unwrap().0.to_bits(), expected.to_bits(), "{text}");
+            let expected: f32 = text.parse().unwrap();
+            assert_eq!(parse_f32(text.as_bytes()).unwrap().0.to_bits(), expected.to_bits(), "{text}");
+        }
+    }
+
+    #[test]
+    fn boundaries() {
+        assert_eq!(parse_float(b"9007199254740993"), Some((9007199254740992.0, 16)));
+        assert_eq!(parse_float(b"9007199254740993.00000000000000000000000000000000000001"), Some((9007199254740994.0, 55)));
+        assert_eq!(parse_float(b"1.797693134862315807e308"), Some((f64::MAX, 24)));
+        assert_eq!(parse_float(b"1.797693134862315808e308"), Some((f64::INFINITY, 24)));
+        assert_eq!(parse_float(b"1.7976931348623158e308"), Some((f64::MAX, 22)));
+        assert_eq!(parse_float(b"2.4703282292062328e-324"), Some((5e-324, 23)));
+        assert_eq!(parse_float(b"2.4703282292062327e-324"), Some((0.0, 23)));
+    }
+}
@@ -0,0 +1,737 @@
+//! Double-Double arithmetic routines
+//!
+//! \[1\]
+//! Mioara Joldes, Jean-Michel Muller, Valentina Popescu.
+//! Tight and rigourous error bounds for basic building blocks of double-word arithmetic, 2017.
+//! <https://hal.science/hal-01351529v3/document>
+//!
+//! \[2\]
+//! T. J. Dekker, A Floating-Point Technique for Extending the Available Precision, 1971
+//! <https://csclub.uwaterloo.ca/~pbarfuss/dekker1971.pdf>
+//!
+//! \[3\]
+//! Yozo Hida, Xiaoye Li, David Bailey. Library for Double-Double and Quad-Double Arithmetic, 2000.
+//! <http://web.mit.edu/tabbott/Public/quaddouble-debian/qd-2.3.4-old/docs/qd.pdf>
+//!
+//! \[4\]
+//! Laurent Thevenoux, Philippe Langlois, Matthieu Martel.
+//! Automatic Source-to-Source Error Compensation of Floating-Point Programs
+//! <https://hal.science/hal-01158399/document>
+//!
+//! \[5\]
+//! Jonathan Richard Shewchuk
+//! Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates, 1997
+//! <https://people.eecs.berkeley.edu/~jrs/papers/robustr.pdf>
+//!
+//! \[6\]
+//! Vincent Lefèvre, Nicolas Louvet, Jean-Michel Muller, Joris Picot, Laurence Rideau.
+//! Accurate Calculation of Euclidean Norms Using Double-word Arithmetic, 2023.
+//! <https://hal.science/hal-03482567/document>
+//!
+//! Error bounds of operations are relative, with `u = 2^-53`, for results without overflow
+//! and underflow:
+//!
+//! | Operation                     | Algorithm              | Error bound      |
+//! |-------------------------------|------------------------|------------------|
+//! | `DoubleDouble + f64`          | DWPlusFP \[1\]         | `2u²`            |
+//! | `DoubleDouble + DoubleDouble` | AccurateDWPlusDW \[1\] | `3u² / (1 - 4u)` |
+//! | `DoubleDouble * f64`          | DWTimesFP1 \[1\]       | `3u² / 2 + 4u³`  |
+//! | `DoubleDouble * DoubleDouble` | DWTimesDW1 \[1\]       | `7u²`            |
+//! | `DoubleDouble / f64`          | DWDivFP2 \[1\]         | `3.5u²`          |
+//! | `DoubleDouble / DoubleDouble` | DWDivDW2 \[1\]         | `15u² + 56u³`    |
+//! | [`DoubleDouble::sqrt`]        | SQRTDWtoDW \[6\]       | `25u² / 8`       |
+//!
+//! ```
+//! use pure_parse_float::DoubleDouble;
+//!
+//! let two = DoubleDouble::from(2.0);
+//! let root = two.sqrt();
+//! assert_eq!(root.hi, 2f64.sqrt());
+//! assert!((root * root - two).hi.abs() < 1e-31);
+//! assert_eq!(format!("{root:.29}"), "1.41421356237309504880168872421e0");
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::bigint::{exact_digits, round_digits};
+
+// Significant digits of Display without precision
+const DISPLAY_DIGITS: usize = 32;
+// Significant digits of Display with precision, the rest are zeros, 106 bits of Double-Double
+// have about 32 digits
+const MAX_DISPLAY_DIGITS: usize = 34;
+
+/// Unevaluated sum `hi + lo` of two f64 values, where `hi` is the properly rounded value
+/// and `lo` is the remainder.
+///
+/// 31 digits garantee, with (exp^10 >= -291) or (exp^2 >= -968)
+///
+/// Comparisons are by the exact value `hi + lo`, so pairs which are not normalized are
+/// compared right too.
+#[derive(Clone, Copy, Debug)]
+pub struct DoubleDouble {
+    /// Rounded value
+    pub hi: f64,
+    /// Remainder, `|lo| <= ulp(hi) / 2`
+    pub lo: f64,
+}
+
+impl From<f64> for DoubleDouble {
+    fn from(value: f64) -> Self {
+        DoubleDouble{
+            hi: value,
+            lo: 0.0,
+        }
+    }
+}
+
+impl Add<f64> for DoubleDouble {
+    type Output = Self;
+
+    // The DWPlusFP algorithm [1]
+    fn add(self, rhs: f64) -> Self::Output {
+        let result = Self::two_sum(self.hi, rhs);
+
+        // infinity check
+        if result.hi.is_infinite() {
+            return result.hi.into();
+        }
+
+        Self::fast_two_sum(result.hi, result.lo + self.lo)
+    }
+}
+
+impl Mul<f64> for DoubleDouble {
+    type Output = Self;
+
+    // The "DWTimesFP1" algorithm [1]
+    fn mul(self, rhs: f64) -> Self::Output {
+        let c = DoubleDouble::two_product(self.hi, rhs);
+
+        // infinity check
+        if c.hi.is_infinite() {
+            return c.hi.into();
+        }
+
+        let result = DoubleDouble::fast_two_sum(c.hi, self.lo * rhs);
+        DoubleDouble::fast_two_sum(result.hi, result.lo + c.lo)
+    }
+}
+
+impl MulAssign<f64> for DoubleDouble {
+    fn mul_assign(&mut self, rhs: f64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div<f64> for DoubleDouble {
+    type Output = Self;
+
+    // The "DWDivFP2" algorithm [1]
+    fn div(self, rhs: f64) -> Self::Output {
+        let hi = self.hi / rhs;
+
+        // infinity check
+        if hi.is_infinite() {
+            return hi.into();
+        }
+
+        let p = DoubleDouble::two_product(hi, rhs);
+
+        let dhi = self.hi - p.hi;
+        let d = DoubleDouble{
+            hi: dhi,
+            lo: dhi - p.lo,
+        };
+
+        let result = DoubleDouble{
+            hi,
+            lo: (d.lo + self.lo) / rhs,
+        };
+
+        DoubleDouble::fast_two_sum(result.hi, result.lo)
+    }
+}
+
+impl DivAssign<f64> for DoubleDouble {
+    fn div_assign(&mut self, rhs: f64) {
+        *self = *self / rhs;
+    }
+}
+
+impl Add for DoubleDouble {
+    type Output = Self;
+
+    // The "AccurateDWPlusDW" algorithm [1]
+    fn add(self, rhs: Self) -> Self::Output {
+        let s = Self::two_sum(self.hi, rhs.hi);
+
+        // infinity check
+        if s.hi.is_infinite() {
+            return s.hi.into();
+        }
+
+        let t = Self::two_sum(self.lo, rhs.lo);
+        let c = s.lo + t.hi;
+        let v = Self::fast_two_sum(s.hi, c);
+        let w = t.lo + v.lo;
+        Self::fast_two_sum(v.hi, w)
+    }
+}
+
+impl AddAssign for DoubleDouble {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for DoubleDouble {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl SubAssign for DoubleDouble {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<f64> for DoubleDouble {
+    type Output = Self;
+
+    fn sub(self, rhs: f64) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for DoubleDouble {
+    type Output = Self;
+
+    // The "DWTimesDW1" algorithm [1]
+    fn mul(self, rhs: Self) -> Self::Output {
+        let c = Self::two_product(self.hi, rhs.hi);
+
+        // infinity check
+        if c.hi.is_infinite() {
+            return c.hi.into();
+        }
+
+        let tl1 = self.hi * rhs.lo;
+        let tl2 = self.lo * rhs.hi;
+        let cl2 = tl1 + tl2;
+        let cl3 = c.lo + cl2;
+        Self::fast_two_sum(c.hi, cl3)
+    }
+}
+
+impl MulAssign for DoubleDouble {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<f64> for &DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl Div for DoubleDouble {
+    type Output = Self;
+
+    // The "DWDivDW2" algorithm [1]
+    fn div(self, rhs: Self) -> Self::Output {
+        let th = self.hi / rhs.hi;
+
+        // infinity check
+        if th.is_infinite() {
+            return th.into();
+        }
+
+        let r = rhs * th;
+        let pi = Self::two_sum(self.hi, -r.hi);
+        let dl = self.lo - r.lo;
+        let d = pi.lo + dl;
+        let d = pi.hi + d;
+        let tl = d / rhs.hi;
+        Self::fast_two_sum(th, tl)
+    }
+}
+
+impl DivAssign for DoubleDouble {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Div<f64> for &DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        *self / rhs
+    }
+}
+
+impl Neg for DoubleDouble {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        DoubleDouble{
+            hi: -self.hi,
+            lo: -self.lo,
+        }
+    }
+}
+
+impl PartialEq for DoubleDouble {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for DoubleDouble {
+    // 2Sum gives the same normalized pair for the same value hi + lo, and Hi of the pairs
+    // is the rounded value, so pairs are in the order of Hi, then of Lo
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let left = Self::two_sum(self.hi, self.lo);
+        let right = Self::two_sum(other.hi, other.lo);
+        match left.hi.partial_cmp(&right.hi)? {
+        Ordering::Equal => left.lo.partial_cmp(&right.lo),
+        ordering => Some(ordering),
+        }
+    }
+}
+
+impl PartialEq<f64> for DoubleDouble {
+    fn eq(&self, other: &f64) -> bool {
+        *self == DoubleDouble::from(*other)
+    }
+}
+
+impl PartialOrd<f64> for DoubleDouble {
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        self.partial_cmp(&DoubleDouble::from(*other))
+    }
+}
+
+/// Scientific notation like `{:e}` of f64, correctly rounded with ties to even: 32 significant
+/// digits without trailing zeros, or Precision digits after the point, where digits past
+/// the 34th significant digit are zeros.
+/// Zero, Inf and NaN are written as `hi`.
+///
+/// ```
+/// use pure_parse_float::DoubleDouble;
+///
+/// let third = DoubleDouble::from(1.0) / DoubleDouble::from(3.0);
+/// assert_eq!(format!("{third}"), "3.3333333333333333333333333333333e-1");
+/// assert_eq!(format!("{:.3}", -third), "-3.333e-1");
+/// assert_eq!(format!("{}", DoubleDouble::from(1500.0)), "1.5e3");
+/// assert_eq!(format!("{:>+8}", DoubleDouble::from(1500.0)), "  +1.5e3");
+/// ```
+impl fmt::Display for DoubleDouble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // normalized, sign of zero is kept
+        let number = if self.lo == 0.0 { *self } else { Self::two_sum(self.hi, self.lo) };
+        if !number.hi.is_finite() || number.hi == 0.0 {
+            return fmt::LowerExp::fmt(&number.hi, f);
+        }
+
+        let count = f.precision().map_or(DISPLAY_DIGITS, |precision| precision.saturating_add(1));
+        let (mut digits, exponent) = if number.hi > 0.0 {
+            sum_digits(number.hi, number.lo, count.min(MAX_DISPLAY_DIGITS))
+        } else {
+            sum_digits(-number.hi, -number.lo, count.min(MAX_DISPLAY_DIGITS))
+        };
+        if f.precision().is_some() {
+            digits.resize(count, 0);
+        } else {
+            while digits.len() > 1 && digits.last() == Some(&0) {
+                digits.pop();
+            }
+        }
+
+        // digits without sign, the sign, fill, alignment and width are written like in integers
+        let mut text = String::new();
+        text.push((b'0' + digits[0]) as char);
+        if digits.len() > 1 {
+            text.push('.');
+            text.extend(digits[1..].iter().map(|&digit| (b'0' + digit) as char));
+        }
+        text.push_str(&format!("e{exponent}"));
+        f.pad_integral(number.hi > 0.0, "", &text)
+    }
+}
+
+// Count first digits of positive Hi + Lo, |Lo| < Hi, correctly rounded with ties to even,
+// with exponent of the first digit
+fn sum_digits(hi: f64, lo: f64, count: usize) -> (Vec<u8>, isize) {
+    let (hi_digits, hi_exponent) = exact_digits(hi);
+
+    // exact sum of decimal digits, leading zero for carry
+    let mut digits: Vec<u8> = std::iter::once(0).chain(hi_digits).collect();
+    let mut exponent = hi_exponent + 1;
+    if lo != 0.0 {
+        let (lo_digits, lo_exponent) = exact_digits(lo.abs());
+        let offset = (exponent - lo_exponent) as usize;
+        digits.resize(digits.len().max(offset + lo_digits.len()), 0);
+        let mut carry = 0;
+        for i in (0..digits.len()).rev() {
+            let digit = i.checked_sub(offset).and_then(|j| lo_digits.get(j)).copied().unwrap_or(0) as i8;
+            let mut sum = digits[i] as i8 + if lo > 0.0 { digit } else { -digit } + carry;
+            carry = 0;
+            if sum >= 10 {
+                sum -= 10;
+                carry = 1;
+            } else if sum < 0 {
+                sum += 10;
+                carry = -1;
+            }
+            digits[i] = sum as u8;
+        }
+    }
+    let zeros = digits.iter().take_while(|&&digit| digit == 0).count();
+    digits.drain(..zeros);
+    exponent -= zeros as isize;
+
+    let mut digits = round_digits(&digits, count as isize);
+    // carry to the next power of ten
+    if digits.len() > count {
+        digits.pop();
+        exponent += 1;
+    }
+    (digits, exponent)
+}
+
+impl From<DoubleDouble> for f64 {
+    fn from(value: DoubleDouble) -> Self {
+        value.hi
+    }
+}
+
+impl DoubleDouble {
+    /// Exact sum of A and B, condition: `|a| >= |b|`.
+    /// The "Fast2Sum" algorithm (Dekker 1971) \[1\].
+    pub fn fast_two_sum(a: f64, b: f64) -> Self {
+        let hi = a + b;
+
+        // infinity check
+        if hi.is_infinite() {
+            return hi.into();
+        }
+
+        Self{
+            hi,
+            lo: b - (hi - a),
+        }
+    }
+
+    /// Exact sum of A and B, without condition.
+    /// The "2Sum" algorithm \[1\].
+    pub fn two_sum(a: f64, b: f64) -> Self {
+        let hi = a + b;
+
+        // infinity check
+        if hi.is_infinite() {
+            return hi.into();
+        }
+
+        let ah = hi - b;
+        let bh = hi - ah;
+
+        Self{
+            hi,
+            lo: (a - ah) + (b - bh),
+        }
+    }
+
+    /// Exact product of A and B, without underflow of the remainder.
+    /// The "TWO-PRODUCT" algorithm \[5\], with fused multiply-add or with the Veltkamp split,
+    /// selected with the `fma` feature, results are the same.
+    pub fn two_product(a: f64, b: f64) -> Self {
+        #[cfg(feature = "fma")]
+        return Self::two_product_fma(a, b);
+        #[cfg(not(feature = "fma"))]
+        return Self::two_product_split(a, b);
+    }
+
+    // The "TWO-PRODUCT" algorithm [5], with fused multiply-add
+    #[cfg_attr(not(feature = "fma"), allow(dead_code))]
+    pub(crate) fn two_product_fma(a: f64, b: f64) -> Self {
+        let hi = a * b;
+
+        // infinity check
+        if hi.is_infinite() {
+            return hi.into();
+        }
+
+        Self{
+            hi,
+            lo: a.mul_add(b, -hi),
+        }
+    }
+
+    // The "TWO-PRODUCT" algorithm [5], with Veltkamp split
+    #[cfg_attr(feature = "fma", allow(dead_code))]
+    pub(crate) fn two_product_split(a: f64, b: f64) -> Self {
+        let hi = a * b;
+
+        // infinity check
+        if hi.is_infinite() {
+            return hi.into();
+        }
+
+        // near overflow products of halves can overflow, so compute with scaled operand
+        // 2^1000 < |A * B| < 2^1024, larger operand is normal, scaling by power of two is exact
+        const INFINITY_PRODUCT: f64 = 1.0715086071862673e301; // = 2^1000
+        const PRODUCT_DOWN: f64 = 1.1102230246251565e-16; // = 2^-53
+        const PRODUCT_UP: f64 = 9007199254740992.0; // = 2^53
+        if hi.abs() > INFINITY_PRODUCT {
+            let product = if a.abs() > b.abs() {
+                Self::two_product_split(a * PRODUCT_DOWN, b)
+            } else {
+                Self::two_product_split(a, b * PRODUCT_DOWN)
+            };
+            return Self{
+                hi: product.hi * PRODUCT_UP,
+                lo: product.lo * PRODUCT_UP,
+            };
+        }
+
+        let a2 = Self::split(a);
+        let b2 = Self::split(b);
+
+        let err1 = hi - a2.hi * b2.hi;
+        let err2 = err1 - a2.lo * b2.hi;
+        let err3 = err2 - a2.hi * b2.lo;
+
+        Self{
+            hi,
+            lo: a2.lo * b2.lo - err3,
+        }
+    }
+
+    /// Square root, NaN for negative values.
+    /// The "SQRTDWtoDW" algorithm \[6\].
+    pub fn sqrt(self) -> Self {
+        if !(self.hi > 0.0 && self.hi.is_finite()) {
+            return self.hi.sqrt().into();
+        }
+
+        let sh = self.hi.sqrt();
+        // exact remainder Hi - Sh^2
+        let p = Self::two_product(sh, sh);
+        let r1 = (self.hi - p.hi) - p.lo;
+        let r2 = self.lo + r1;
+        let sl = r2 / (2.0 * sh);
+        Self::fast_two_sum(sh, sl)
+    }
+
+    // The "Veltkamp Split" algorithm [2] [3] [4]
+    // See "Splitting into Halflength Numbers" and ALGOL procedure "mul12" in Appendix in [2]
+    #[allow(clippy::excessive_precision)]
+    fn split(a: f64) -> Self {
+        // The Splitter should be chosen equal to 2^trunc(t - t / 2) + 1,
+        // where t is the number of binary digits in the mantissa.
+        const SPLITTER: f64 = 134217729.0;// = 2^(53 - 53 div 2) + 1 = 2^27 + 1
+        // Just make sure we don't have an overflow for Splitter,
+        // InfinitySplit is 2^(e - (t - t div 2))
+        // where e is max exponent, t is number of binary digits.
+        const INFINITY_SPLIT: f64 = 6.69692879491417e+299;// = 2^(1023 - (53 - 53 div 2)) = 2^996
+        // just multiply by the next lower power of two to get rid of the overflow
+        // 2^(+/-)27 + 1 = 2^(+/-)28
+        const INFINITY_DOWN: f64 = 3.7252902984619140625e-09;// = 2^-(27 + 1) = 2^-28
+        const INFINITY_UP: f64 = 268435456.0;// = 2^(27 + 1) = 2^28
+
+        if !(-INFINITY_SPLIT..=INFINITY_SPLIT).contains(&a) {
+            // down
+            let a = a * INFINITY_DOWN;
+            // mul
+            let temp = SPLITTER * a;
+            let hi = temp + (a - temp);
+            let lo = a - hi;
+            // up
+            return Self{
+                hi: hi * INFINITY_UP,
+                lo: lo * INFINITY_UP,
+            };
+        }
+
+        let temp = SPLITTER * a;
+        let hi = temp + (a - temp);
+        Self{
+            hi,
+            lo: a - hi,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DoubleDouble;
+    use crate::format_scientific;
+    use crate::random::random_generator;
+
+    const U2: f64 = 1.232595164407831e-32; // = 2^-106
+
+    // Random Double-Double of integer with Bits bits at most, with exact value
+    fn random_integer(next: &mut impl FnMut() -> u64, bits: u32) -> (DoubleDouble, i128) {
+        let hi = (next() >> (64 - bits)) as i128;
+        let lo = (next() >> (64 - bits + 10 + (next() % 30) as u32)) as i128;
+        let value = if next().is_multiple_of(2) { hi + lo } else { -hi + lo };
+        let number = DoubleDouble::two_sum(value as f64, (value - value as f64 as i128) as f64);
+        assert_eq!(number.hi as i128 + number.lo as i128, value);
+        (number, value)
+    }
+
+    // Random normalized Double-Double with exponent from -250 to 250
+    fn random(next: &mut impl FnMut() -> u64) -> DoubleDouble {
+        let hi = f64::from_bits((next() >> 12) | ((next() % 500 + 773) << 52) | (next() & (1 << 63)));
+        let lo = hi * ((next() >> 11) as f64 / 9007199254740992.0 - 0.5) / 9007199254740992.0;
+        DoubleDouble::two_sum(hi, lo)
+    }
+
+    // Relative error of Number to exact integer Value is at most Bound u²
+    fn assert_error(number: DoubleDouble, value: i128, bound: f64) {
+        let error = number.hi as i128 + number.lo as i128 - value;
+        assert!(error.abs() as f64 <= bound * U2 * value.abs() as f64, "{number:?} {value} {error}");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut next = random_generator(0x6A09E667F3BCC908);
+        for _ in 0..100_000 {
+            // exact integers
+            let (x, a) = random_integer(&mut next, 63);
+            let (y, b) = random_integer(&mut next, 63);
+            assert_error(x + y, a + b, 3.0 + 1e-10);
+            assert_error(x - y, a - b, 3.0 + 1e-10);
+            assert_error(x * y, a * b, 7.0);
+
+            // inverse operations
+            let x = random(&mut next);
+            let y = random(&mut next);
+            let d = (x / y) * y - x;
+            assert!(d.hi.abs() <= 23.0 * U2 * x.hi.abs(), "{x:?} / {y:?}");
+            let x = if x.hi < 0.0 { -x } else { x };
+            let r = x.sqrt();
+            let d = r * r - x;
+            assert!(d.hi.abs() <= 14.0 * U2 * x.hi.abs(), "sqrt {x:?}");
+            assert!(r.lo.abs() <= (r.hi.next_up() - r.hi) / 2.0, "sqrt {x:?}");
+        }
+
+        let one = DoubleDouble::from(1.0);
+        let third = one / DoubleDouble::from(3.0);
+        assert_eq!(third, DoubleDouble{ hi: 0.3333333333333333, lo: 1.850371707708594e-17 });
+        assert!((third * DoubleDouble::from(3.0) - one).hi.abs() <= 7.0 * U2);
+        assert!((third + third + third - one).hi.abs() <= 6.0 * U2);
+        assert!((third * 3.0 - 1.0).hi.abs() <= 2.0 * U2);
+        assert_eq!(DoubleDouble::from(9.0).sqrt(), 3.0);
+        assert_eq!(DoubleDouble::from(0.0).sqrt(), 0.0);
+        assert!(DoubleDouble::from(-1.0).sqrt().hi.is_nan());
+        assert_eq!(DoubleDouble::from(f64::INFINITY).sqrt(), f64::INFINITY);
+        assert_eq!(DoubleDouble::from(f64::MAX) + DoubleDouble::from(f64::MAX), f64::INFINITY);
+        assert_eq!(DoubleDouble::from(f64::MAX) * DoubleDouble::from(2.0), f64::INFINITY);
+        assert_eq!(one / DoubleDouble::from(0.0), f64::INFINITY);
+        assert_eq!(one / DoubleDouble::from(-0.0), f64::NEG_INFINITY);
+
+        let mut number = third;
+        number += one;
+        number -= third;
+        number *= DoubleDouble::from(4.0);
+        number /= DoubleDouble::from(8.0);
+        assert!((number - 0.5).hi.abs() <= 4.0 * U2);
+        assert_eq!((third / 0.5).hi, 0.6666666666666666);
+    }
+
+    #[test]
+    fn comparisons() {
+        let one = DoubleDouble::from(1.0);
+        let above = DoubleDouble{ hi: 1.0, lo: 1e-20 };
+        let below = DoubleDouble{ hi: 1.0, lo: -1e-20 };
+        assert!(below < one && one < above && below < above);
+        assert!(above > 1.0 && below < 1.0 && one == 1.0 && one >= 1.0);
+        assert!(-above < -one && -above == DoubleDouble{ hi: -1.0, lo: -1e-20 });
+        assert!(DoubleDouble::from(0.0) == -0.0);
+        assert!(DoubleDouble::from(f64::NAN).partial_cmp(&one).is_none());
+        assert!(DoubleDouble::from(f64::NEG_INFINITY) < below);
+
+        // pairs which are not normalized are compared by value
+        let two = DoubleDouble{ hi: 1.0, lo: 1.0 };
+        let below_two = DoubleDouble{ hi: 2.0, lo: -1e-300 };
+        assert!(two > below_two && two != below_two);
+        assert!(below_two < two);
+        assert!(two == 2.0 && two == DoubleDouble{ hi: 1.5, lo: 0.5 } && two > 1.5);
+        assert!(DoubleDouble{ hi: 1.0, lo: 2f64.powi(-53) } == DoubleDouble{ hi: 1.0 + f64::EPSILON, lo: -2f64.powi(-53) });
+        assert!(DoubleDouble{ hi: f64::NAN, lo: 0.0 } != DoubleDouble{ hi: f64::NAN, lo: 0.0 });
+    }
+
+    #[test]
+    fn display() {
+        let pi = DoubleDouble{ hi: std::f64::consts::PI, lo: 1.2246467991473532e-16 };
+        assert_eq!(format!("{pi}"), "3.1415926535897932384626433832795e0");
+        // 34 significant digits, then zeros
+        assert_eq!(format!("{:.33}", -pi), "-3.141592653589793238462643383279506e0");
+        assert_eq!(format!("{:.40}", -pi), "-3.1415926535897932384626433832795060000000e0");
+        assert!(format!("{:.1000}", pi).ends_with(&format!("3279506{}e0", "0".repeat(967))));
+        assert_eq!(format!("{:.0}", pi), "3e0");
+        let below = DoubleDouble{ hi: 1.0, lo: -8.673617379884035e-19 }; // = 1 - 2^-60
+        assert_eq!(format!("{below}"), "9.999999999999999991326382620116e-1");
+        assert_eq!(format!("{:.1}", below), "1.0e0");
+        assert_eq!(format!("{:.17}", below), "9.99999999999999999e-1");
+        assert_eq!(format!("{:.16}", below), "1.0000000000000000e0");
+        assert_eq!(format!("{}", DoubleDouble{ hi: 1e300, lo: 1e283 }), "1.0000000000000000625047602552044e300");
+        assert_eq!(format!("{}", DoubleDouble::from(5e-324)), "4.9406564584124654417656879286822e-324");
+        for value in [0.0, -0.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            assert_eq!(format!("{}", DoubleDouble::from(value)), format!("{value:e}"));
+        }
+        assert_eq!(format!("{}", DoubleDouble{ hi: 1.0, lo: -1.0 }), "0e0");
+
+        // width, fill, alignment and sign
+        let third = DoubleDouble::from(1.0) / DoubleDouble::from(3.0);
+        assert_eq!(format!("{:>12.3}", third), "    3.333e-1");
+        assert_eq!(format!("{:*<12.3}", -third), "-3.333e-1***");
+        assert_eq!(format!("{:^+12.3}", third), " +3.333e-1  ");
+        assert_eq!(format!("{:012.3}", -third), "-0003.333e-1");
+        assert_eq!(format!("{:5.3}", third), "3.333e-1");
+        assert_eq!(format!("{:>8}", DoubleDouble::from(-0.0)), format!("{:>8e}", -0.0));
+        assert_eq!(format!("{:+}", DoubleDouble::from(f64::INFINITY)), "+inf");
+
+        // same digits as printf for Doubles
+        let mut next = random_generator(0xBB67AE8584CAA73B);
+        for _ in 0..10_000 {
+            let value = f64::from_bits(next() >> 1);
+            if !value.is_finite() {
+                continue;
+            }
+            let precision = (next() % 34) as usize;
+            let text = format!("{:.*}", precision, DoubleDouble::from(value));
+            let expected = format_scientific(value, precision + 1);
+            let (mantissa, exponent) = expected.split_once('e').unwrap();
+            assert_eq!(text, format!("{mantissa}e{}", exponent.parse::<i32>().unwrap()));
+        }
+    }
+
+    #[test]
+    fn fma_and_split_products_match() {
+        let mut next = random_generator(0x9E3779B97F4A7C15);
+        for _ in 0..100_000 {
+            // exponents in range without underflow of the product error
+            let a = f64::from_bits((next() >> 12) | ((next() % 650 + 700) << 52));
+            let b = f64::from_bits((next() >> 12) | ((next() % 650 + 700) << 52));
+            for b in [b, 10.0, 1e22, 1e15, f64::MAX / a] {
+                let fma = DoubleDouble::two_product_fma(a, b);
+                let split = DoubleDouble::two_product_split(a, b);
+                assert_eq!(fma.hi.to_bits(), split.hi.to_bits(), "{a:e} * {b:e}");
+                assert_eq!(fma.lo, split.lo, "{a:e} * {b:e}");
+            }
+        }
+    }
+}
+
@@ -0,0 +1,46 @@
+//! Errors of the strict parse functions
+
+use std::fmt;
+
+/// Error of [`try_parse_float`](crate::try_parse_float) and
+/// [`parse_float_exact`](crate::parse_float_exact), positions are byte offsets into the text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseFloatError {
+    /// Text is empty
+    Empty,
+    /// Text ends before any digit was read, like `"-"` or `"."`
+    NoDigits,
+    /// Char at the position can not start or continue the float point pattern
+    InvalidCharacter(usize),
+    /// Exponent char at the position is not followed by exponent digits, like `"500e"`
+    DanglingExponent(usize),
+    /// Finite number is too large for Double and converted to infinity
+    Overflow,
+    /// Non zero number is too small for Double and converted to zero
+    Underflow,
+}
+
+impl ParseFloatError {
+    /// Byte offset of the offending char, if the error has one
+    pub fn position(&self) -> Option<usize> {
+        match *self {
+        ParseFloatError::InvalidCharacter(position) | ParseFloatError::DanglingExponent(position) => Some(position),
+        _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+        ParseFloatError::Empty => write!(f, "cannot parse float from empty text"),
+        ParseFloatError::NoDigits => write!(f, "no digits in float"),
+        ParseFloatError::InvalidCharacter(position) => write!(f, "invalid character at byte {position}"),
+        ParseFloatError::DanglingExponent(position) => write!(f, "exponent without digits at byte {position}"),
+        ParseFloatError::Overflow => write!(f, "float overflow to infinity"),
+        ParseFloatError::Underflow => write!(f, "float underflow to zero"),
+        }
+    }
+}
+
+impl std::error::Error for ParseFloatError {}
@@ -0,0 +1,456 @@
+//! C ABI exports of the dynamic library
+
+#[cfg(feature = "correct-rounding")]
+use std::cmp::Ordering;
+use std::ffi::{c_int, c_char, c_double, c_float};
+
+#[cfg(feature = "correct-rounding")]
+use crate::correct_rounding::BinaryFloat;
+use crate::reader::{CodeUnit, NulReader};
+use crate::format::{write_fixed, write_scientific, write_shortest, PaddedText};
+use crate::{read_number, ParseFloatError, ParseOptions};
+#[cfg(feature = "correct-rounding")]
+use crate::Number;
+
+// Error codes written by parse_float_ex
+const PARSE_FLOAT_OK: c_int = 0;
+const PARSE_FLOAT_EMPTY: c_int = 1;
+const PARSE_FLOAT_NO_DIGITS: c_int = 2;
+const PARSE_FLOAT_INVALID_CHARACTER: c_int = 3;
+const PARSE_FLOAT_DANGLING_EXPONENT: c_int = 4;
+const PARSE_FLOAT_OVERFLOW: c_int = 5;
+const PARSE_FLOAT_UNDERFLOW: c_int = 6;
+
+fn error_code(error: ParseFloatError) -> c_int {
+    match error {
+    ParseFloatError::Empty => PARSE_FLOAT_EMPTY,
+    ParseFloatError::NoDigits => PARSE_FLOAT_NO_DIGITS,
+    ParseFloatError::InvalidCharacter(_) => PARSE_FLOAT_INVALID_CHARACTER,
+    ParseFloatError::DanglingExponent(_) => PARSE_FLOAT_DANGLING_EXPONENT,
+    ParseFloatError::Overflow => PARSE_FLOAT_OVERFLOW,
+    ParseFloatError::Underflow => PARSE_FLOAT_UNDERFLOW,
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn parse_float(text: *const c_char, value: *mut c_double, text_end: *mut *const c_char) -> c_int {
+    match crate::parse_float_impl(NulReader::new(text as *const u8)) {
+    Some((res, end)) => {
+        *value = res;
+        *text_end = text.add(end);
+        1
+    }
+    None => 0
+    }
+}
+
+// Same as parse_float, but converts to Single without double rounding through Double
+#[no_mangle]
+unsafe extern "C" fn parse_float32(text: *const c_char, value: *mut c_float, text_end: *mut *const c_char) -> c_int {
+    match read_number(NulReader::new(text as *const u8), &ParseOptions::new(), false) {
+    Ok((number, end)) => {
+        *value = number.to_f32();
+        *text_end = text.add(end);
+        1
+    }
+    Err(_) => 0
+    }
+}
+
+// Same as parse_float, but writes Double-Double Hi + Lo, Hi is the same as Value of parse_float
+#[no_mangle]
+unsafe extern "C" fn parse_double_double(text: *const c_char, hi: *mut c_double, lo: *mut c_double, text_end: *mut *const c_char) -> c_int {
+    match read_number(NulReader::new(text as *const u8), &ParseOptions::new(), false) {
+    Ok((number, end)) => {
+        let res = number.to_double_double();
+        *hi = res.hi;
+        *lo = res.lo;
+        *text_end = text.add(end);
+        1
+    }
+    Err(_) => 0
+    }
+}
+
+// Parse null terminated text of code units like parse_float
+unsafe fn parse_units<T: CodeUnit>(text: *const T, value: *mut c_double, text_end: *mut *const T) -> c_int {
+    match read_number(NulReader::new(text), &ParseOptions::new(), false) {
+    Ok((number, end)) => {
+        *value = number.to_f64();
+        *text_end = text.add(end);
+        1
+    }
+    Err(_) => 0
+    }
+}
+
+// Same as parse_float for UTF-16 text, like wchar_t on Windows
+#[no_mangle]
+unsafe extern "C" fn parse_float_w(text: *const u16, value: *mut c_double, text_end: *mut *const u16) -> c_int {
+    parse_units(text, value, text_end)
+}
+
+// Same as parse_float for UTF-32 text, like wchar_t on Linux
+#[no_mangle]
+unsafe extern "C" fn parse_float_u32(text: *const u32, value: *mut c_double, text_end: *mut *const u32) -> c_int {
+    parse_units(text, value, text_end)
+}
+
+// Same as parse_float, but writes error code to Error.
+// On overflow and underflow Value and TextEnd are written like on success, but result is 0.
+// On other errors Value is unmodified and TextEnd points at the offending char, or equals Text.
+#[no_mangle]
+unsafe extern "C" fn parse_float_ex(text: *const c_char, value: *mut c_double, text_end: *mut *const c_char, error: *mut c_int) -> c_int {
+    match read_number(NulReader::new(text as *const u8), &ParseOptions::new(), false) {
+    Ok((number, end)) => {
+        let res = number.to_f64();
+        *value = res;
+        *text_end = text.add(end);
+        match number.check_range(res) {
+        Ok(_) => {
+            *error = PARSE_FLOAT_OK;
+            1
+        }
+        Err(e) => {
+            *error = error_code(e);
+            0
+        }
+        }
+    }
+    Err(e) => {
+        *text_end = text.add(e.position().unwrap_or(0));
+        *error = error_code(e);
+        0
+    }
+    }
+}
+
+// Value of ERANGE in Linux, Apple, BSD and Windows C libraries
+#[cfg(feature = "correct-rounding")]
+const ERANGE: c_int = 34;
+
+// Thread local errno of the C library
+#[cfg(all(feature = "correct-rounding", any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd", windows)))]
+extern "C" {
+    #[cfg_attr(target_os = "linux", link_name = "__errno_location")]
+    #[cfg_attr(target_os = "android", link_name = "__errno")]
+    #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "freebsd"), link_name = "__error")]
+    #[cfg_attr(windows, link_name = "_errno")]
+    fn errno_location() -> *mut c_int;
+}
+
+#[cfg(all(feature = "correct-rounding", any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd", windows)))]
+unsafe fn set_range_error() {
+    *errno_location() = ERANGE;
+}
+
+#[cfg(all(feature = "correct-rounding", not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd", windows))))]
+unsafe fn set_range_error() {}
+
+// Result is out of range like in glibc strtod: finite number going to infinity, or inexact
+// result of a tiny number, with tininess after rounding, see FixedDecimal::is_tiny
+#[cfg(feature = "correct-rounding")]
+fn is_range_error(number: &Number, value: f64) -> bool {
+    match number {
+    Number::Special(_) => false,
+    Number::Decimal(decimal) => {
+        let abs = value.abs();
+        if abs.is_infinite() {
+            return true;
+        }
+        if !decimal.is_tiny(abs) {
+            return false;
+        }
+        // out of range decimal is below the half of the min Double, exact comparison of it
+        // would take the time of the exponent
+        if !decimal.is_in_range() {
+            return true;
+        }
+        let (mantissa, exponent) = abs.parts();
+        decimal.compare(mantissa, exponent) != Ordering::Equal
+    }
+    Number::Hex(hex) => value.is_infinite() || hex.is_tiny_inexact(),
+    }
+}
+
+// Drop-in replacement of C99 strtod in "C" locale, reads the same chars and sets TextEnd
+// the same way. Without conversion returns 0 and TextEnd equals Text, TextEnd can be null.
+// Overflow gives infinity and underflow gives zero, subnormal or the min normal Double, both
+// set errno to ERANGE like glibc on x86-64, on other platforms than Linux, Android, Apple,
+// FreeBSD and Windows errno is not changed. Needs the default `correct-rounding` feature, which
+// keeps enough digits to round every mantissa the same as glibc.
+#[cfg(feature = "correct-rounding")]
+#[no_mangle]
+unsafe extern "C" fn pure_strtod(text: *const c_char, text_end: *mut *mut c_char) -> c_double {
+    let (value, end) = match read_number(NulReader::new(text as *const u8), &ParseOptions::c(), false) {
+    Ok((number, end)) => {
+        let value = number.to_f64();
+        if is_range_error(&number, value) {
+            set_range_error();
+        }
+        (value, end)
+    }
+    Err(_) => (0.0, 0),
+    };
+    if !text_end.is_null() {
+        *text_end = text.add(end) as *mut c_char;
+    }
+    value
+}
+
+// Copy Text to Buffer like snprintf: at most Size - 1 chars and terminating zero,
+// result is the length of the whole text
+unsafe fn write_text(text: &[u8], buffer: *mut c_char, size: usize) -> c_int {
+    if size != 0 {
+        let count = text.len().min(size - 1);
+        std::ptr::copy_nonoverlapping(text.as_ptr() as *const c_char, buffer, count);
+        *buffer.add(count) = 0;
+    }
+    text.len() as c_int
+}
+
+// Write shortest text of Value that parse_float reads back to the same double, like snprintf.
+// Buffer of 26 chars is always enough.
+#[no_mangle]
+unsafe extern "C" fn format_float(value: c_double, buffer: *mut c_char, size: usize) -> c_int {
+    write_text(write_shortest(value).as_bytes(), buffer, size)
+}
+
+// Copy padded Text to Buffer like write_text, zeros are written without storing them,
+// -1 when the length does not fit int, like snprintf with EOVERFLOW
+unsafe fn write_padded(text: &PaddedText, buffer: *mut c_char, size: usize) -> c_int {
+    let len = text.len().and_then(|len| c_int::try_from(len).ok());
+    if size != 0 {
+        let mut count = 0;
+        for c in text.bytes().take(size - 1) {
+            *buffer.add(count) = c as c_char;
+            count += 1;
+        }
+        *buffer.add(count) = 0;
+    }
+    len.unwrap_or(-1)
+}
+
+// Write Value with Decimals digits after the point like printf("%.*f"), negative Decimals is 0
+#[no_mangle]
+unsafe extern "C" fn format_fixed(value: c_double, decimals: c_int, buffer: *mut c_char, size: usize) -> c_int {
+    write_padded(&write_fixed(value, decimals.max(0) as usize), buffer, size)
+}
+
+// Write Value with SignificantDigits digits like printf("%.*e"), at least one digit is written
+#[no_mangle]
+unsafe extern "C" fn format_scientific(value: c_double, significant_digits: c_int, buffer: *mut c_char, size: usize) -> c_int {
+    write_padded(&write_scientific(value, significant_digits.max(1) as usize), buffer, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::{c_char, c_int, CStr, CString};
+    use super::*;
+    #[cfg(all(feature = "correct-rounding", unix, target_env = "gnu"))]
+    use crate::random::{random_generator, EDGE_DECIMALS};
+
+    fn call_ex(text: &[u8]) -> (c_int, f64, usize, c_int) {
+        let mut value = -1.0;
+        let mut text_end: *const c_char = std::ptr::null();
+        let mut error = -1;
+        let text = CStr::from_bytes_with_nul(text).unwrap();
+        let res = unsafe { parse_float_ex(text.as_ptr(), &mut value, &mut text_end, &mut error) };
+        let end = if text_end.is_null() { usize::MAX } else { text_end as usize - text.as_ptr() as usize };
+        (res, value, end, error)
+    }
+
+    #[test]
+    fn parse_float_ex_codes() {
+        assert_eq!(call_ex(b"12.5e1x\0"), (1, 125.0, 6, PARSE_FLOAT_OK));
+        assert_eq!(call_ex(b"\0"), (0, -1.0, 0, PARSE_FLOAT_EMPTY));
+        assert_eq!(call_ex(b"-.\0"), (0, -1.0, 0, PARSE_FLOAT_NO_DIGITS));
+        assert_eq!(call_ex(b"+z\0"), (0, -1.0, 1, PARSE_FLOAT_INVALID_CHARACTER));
+        assert_eq!(call_ex(b"1e999\0"), (0, f64::INFINITY, 5, PARSE_FLOAT_OVERFLOW));
+        assert_eq!(call_ex(b"1e-999\0"), (0, 0.0, 6, PARSE_FLOAT_UNDERFLOW));
+    }
+
+    #[test]
+    fn double_double() {
+        let text = CString::new("0.1 x").unwrap();
+        let (mut hi, mut lo) = (0.0, 0.0);
+        let mut text_end: *const c_char = std::ptr::null();
+        assert_eq!(unsafe { parse_double_double(text.as_ptr(), &mut hi, &mut lo, &mut text_end) }, 1);
+        assert_eq!((hi, text_end), (0.1, text.as_ptr().wrapping_add(3)));
+        assert!((lo + 5.551115123125783e-18).abs() < 1e-33);
+        let text = CString::new("x").unwrap();
+        assert_eq!(unsafe { parse_double_double(text.as_ptr(), &mut hi, &mut lo, &mut text_end) }, 0);
+    }
+
+    #[test]
+    fn wide_text() {
+        let text: Vec<u16> = "-2.5e2 x\0".encode_utf16().collect();
+        let mut value = 0.0;
+        let mut text_end: *const u16 = std::ptr::null();
+        assert_eq!(unsafe { parse_float_w(text.as_ptr(), &mut value, &mut text_end) }, 1);
+        assert_eq!((value, text_end), (-250.0, text[6..].as_ptr()));
+
+        let text: Vec<u32> = "inf\0".chars().map(|c| c as u32).collect();
+        let mut text_end: *const u32 = std::ptr::null();
+        assert_eq!(unsafe { parse_float_u32(text.as_ptr(), &mut value, &mut text_end) }, 1);
+        assert_eq!((value, text_end), (f64::INFINITY, text[3..].as_ptr()));
+        assert_eq!(unsafe { parse_float_u32([0x2212, 0x31, 0].as_ptr(), &mut value, &mut text_end) }, 0);
+
+        // reading stops at the first char after the number
+        let text: Vec<u16> = "7e1;\0".encode_utf16().collect();
+        let mut text_end: *const u16 = std::ptr::null();
+        assert_eq!(unsafe { parse_float_w(text.as_ptr(), &mut value, &mut text_end) }, 1);
+        assert_eq!((value, text_end), (70.0, text[3..].as_ptr()));
+    }
+
+    #[cfg(all(feature = "correct-rounding", unix, target_env = "gnu"))]
+    extern "C" {
+        fn strtod(text: *const c_char, text_end: *mut *mut c_char) -> f64;
+    }
+
+    #[cfg(feature = "correct-rounding")]
+    fn call_strtod(text: &CStr, function: unsafe extern "C" fn(*const c_char, *mut *mut c_char) -> f64) -> (f64, usize) {
+        let mut text_end: *mut c_char = std::ptr::null_mut();
+        let value = unsafe { function(text.as_ptr(), &mut text_end) };
+        (value, text_end as usize - text.as_ptr() as usize)
+    }
+
+    // Errno after the call, cleared before it
+    #[cfg(all(feature = "correct-rounding", unix, target_env = "gnu"))]
+    fn strtod_errno(text: &CStr, function: unsafe extern "C" fn(*const c_char, *mut *mut c_char) -> f64) -> c_int {
+        unsafe {
+            *errno_location() = 0;
+            function(text.as_ptr(), std::ptr::null_mut());
+            *errno_location()
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "correct-rounding", unix, target_env = "gnu"))]
+    fn pure_strtod_errno() {
+        let subnormal = format!("{:.1100}", 3.0 * 5e-324);
+        for (text, expected) in [
+            ("1.5", 0), ("0", 0), ("inf", 0), ("nan", 0), ("1e400", ERANGE), ("-1e400", ERANGE),
+            ("1e-400", ERANGE), ("1e-310", ERANGE), ("2.2250738585072014e-308", 0),
+            ("2.2250738585072011e-308", ERANGE), ("2.2250738585072012e-308", ERANGE),
+            ("2.22507385850720132e-308", 0), ("1e999999", ERANGE), ("1e-999999", ERANGE),
+            ("0x1p-1074", 0), ("0x1.8p-1074", ERANGE), ("0x1p-1080", ERANGE), ("0x1p2000", ERANGE),
+            ("0x1.fffffffffffff8p1023", ERANGE), ("0x1.0000000000001p-1022", 0), ("x", 0),
+            ("0x1.fffffffffffffp-1023", ERANGE), ("0x1.fffffffffffff8p-1023", 0), ("0x1.fffffffffffff9p-1023", 0),
+            (&subnormal, 0),
+        ] {
+            let text = CString::new(text).unwrap();
+            assert_eq!(strtod_errno(&text, pure_strtod), expected, "{text:?}");
+            if text.to_bytes() != subnormal.as_bytes() {
+                assert_eq!(strtod_errno(&text, strtod), expected, "{text:?}");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "correct-rounding")]
+    fn pure_strtod_grammar() {
+        for (text, expected, end) in [
+            (&b" \t\n\x0B\x0C\r-1.5e3x\0"[..], -1500.0, 12), (b"0x1.8p1\0", 3.0, 7), (b"0x\0", 0.0, 1),
+            (b"  infinit\0", f64::INFINITY, 5), (b"-INFINITY\0", f64::NEG_INFINITY, 9), (b"1e+\0", 1.0, 1),
+            (b"snan\0", 0.0, 0), (b" \0", 0.0, 0), (b"+.e1\0", 0.0, 0), (b"1,5\0", 1.0, 1),
+        ] {
+            let text = CStr::from_bytes_with_nul(text).unwrap();
+            assert_eq!(call_strtod(text, pure_strtod), (expected, end), "{text:?}");
+        }
+        let (value, end) = call_strtod(c"nan(0x2a)", pure_strtod);
+        assert_eq!((value.to_bits(), end), (0x7FF8_0000_0000_002A, 9));
+        assert_eq!(unsafe { pure_strtod(c"25".as_ptr(), std::ptr::null_mut()) }, 25.0);
+    }
+
+    #[test]
+    #[cfg(all(feature = "correct-rounding", unix, target_env = "gnu"))]
+    fn pure_strtod_same_as_strtod() {
+        let mut texts: Vec<CString> = EDGE_DECIMALS.map(|text| CString::new(text).unwrap()).to_vec();
+        let words: [&[u8]; 12] = [b" ", b"\t", b"+", b"-", b"0x", b"0X", b"inf", b"InFiNiTy", b"nan", b"NAN(", b"(", b")"];
+        let chars = b"0123456789.eEpPxXaAfFn_ ()";
+        let mut next = random_generator(0xBB67AE8584CAA73B);
+        for _ in 0..20_000 {
+            let mut text = Vec::new();
+            for _ in 0..1 + next() % 12 {
+                if next().is_multiple_of(3) {
+                    text.extend_from_slice(words[(next() % words.len() as u64) as usize]);
+                } else {
+                    text.push(chars[(next() % chars.len() as u64) as usize]);
+                }
+            }
+            texts.push(CString::new(text).unwrap());
+        }
+
+        for text in texts {
+            let (value, end) = call_strtod(&text, pure_strtod);
+            let (expected, expected_end) = call_strtod(&text, strtod);
+            assert_eq!(value.to_bits(), expected.to_bits(), "value of {text:?}: {value:e} instead of {expected:e}");
+            assert_eq!(end, expected_end, "end of {text:?}");
+            assert_eq!(strtod_errno(&text, pure_strtod), strtod_errno(&text, strtod), "errno of {text:?}");
+        }
+    }
+
+    #[test]
+    fn reads_to_terminator() {
+        // reading stops at the first char after the number, the rest is never read
+        let text = *b"12.5e1;\0";
+        let mut value = 0.0;
+        let mut text_end: *const c_char = std::ptr::null();
+        let start = text.as_ptr() as *const c_char;
+        assert_eq!(unsafe { parse_float(start, &mut value, &mut text_end) }, 1);
+        assert_eq!((value, text_end), (125.0, start.wrapping_add(6)));
+        let text = b"1.5\0e3";
+        let start = text.as_ptr() as *const c_char;
+        assert_eq!(unsafe { parse_float(start, &mut value, &mut text_end) }, 1);
+        assert_eq!((value, text_end), (1.5, start.wrapping_add(3)));
+
+        // loop over numbers of one text
+        let text = CString::new("1 -2.5 3e1 0.25x").unwrap();
+        let mut p = text.as_ptr();
+        let mut values = Vec::new();
+        let mut single = 0.0f32;
+        while unsafe { parse_float(p, &mut value, &mut text_end) } == 1 {
+            assert_eq!(unsafe { parse_float32(p, &mut single, &mut text_end) }, 1);
+            assert_eq!(single as f64, value);
+            values.push(value);
+            p = text_end.wrapping_add(1);
+        }
+        assert_eq!(values, [1.0, -2.5, 30.0, 0.25]);
+    }
+
+    #[test]
+    fn format_float_buffer() {
+        let mut buffer = [b'x' as c_char; 32];
+        let len = unsafe { format_float(-1.5e-7, buffer.as_mut_ptr(), buffer.len()) };
+        assert_eq!(len, 7);
+        assert_eq!(unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_bytes(), b"-1.5e-7");
+
+        let len = unsafe { format_float(0.1, buffer.as_mut_ptr(), 2) };
+        assert_eq!(len, 3);
+        assert_eq!(unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_bytes(), b"0");
+        assert_eq!(unsafe { format_float(0.1, std::ptr::null_mut(), 0) }, 3);
+        // 18 digits without correct rounding, parse_float reads the shortest text as infinity
+        let len = unsafe { format_float(f64::MAX, buffer.as_mut_ptr(), buffer.len()) };
+        let text = unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_bytes();
+        assert_eq!(len, text.len() as c_int);
+        assert_eq!(crate::parse_float(text), Some((f64::MAX, text.len())));
+
+        let len = unsafe { format_fixed(-2.675, 2, buffer.as_mut_ptr(), buffer.len()) };
+        assert_eq!(len, 5);
+        assert_eq!(unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_bytes(), b"-2.67");
+        let len = unsafe { format_scientific(1234.5, -1, buffer.as_mut_ptr(), buffer.len()) };
+        assert_eq!(len, 5);
+        assert_eq!(unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_bytes(), b"1e+03");
+
+        // zeros past the exact decimal are not stored, too long text does not fit int
+        let len = unsafe { format_fixed(0.5, c_int::MAX - 2, buffer.as_mut_ptr(), buffer.len()) };
+        assert_eq!(len, c_int::MAX);
+        assert_eq!(unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_bytes(), b"0.50000000000000000000000000000");
+        assert_eq!(unsafe { format_fixed(-0.5, c_int::MAX, buffer.as_mut_ptr(), buffer.len()) }, -1);
+        assert_eq!(unsafe { format_scientific(5e-324, c_int::MAX, std::ptr::null_mut(), 0) }, -1);
+        let len = unsafe { format_scientific(-5e-324, c_int::MAX - 7, buffer.as_mut_ptr(), buffer.len()) };
+        assert_eq!(len, c_int::MAX);
+        assert_eq!(unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_bytes(), b"-4.9406564584124654417656879286");
+    }
+}
@@ -0,0 +1,436 @@
+//! Decimal mantissa and exponent, as read from text before conversion to binary
+
+use crate::double_double::DoubleDouble;
+use crate::error::ParseFloatError;
+use crate::options::ParseOptions;
+use crate::reader::{Reader, TextReader};
+#[cfg(feature = "correct-rounding")]
+use crate::correct_rounding::{is_near_halfway, is_near_halfway_f32};
+
+// Digits used for Double-Double conversion, Max digits in Double value * 2
+const DOUBLE_DOUBLE_DIGITS: usize = 17 * 2;
+#[cfg(not(feature = "correct-rounding"))]
+const FIXED_DECIMAL_DIGITS: usize = DOUBLE_DOUBLE_DIGITS;
+// Exact midpoint between two Doubles has at most 767 significant digits
+#[cfg(feature = "correct-rounding")]
+const FIXED_DECIMAL_DIGITS: usize = 800;
+
+/// Decimal number `d1.d2d3... * 10^exponent` with leading zeros stripped.
+///
+/// Stores up to 34 significant digits (twice the digits of a Double), or 800 digits with
+/// the `correct-rounding` feature, the digits after them are dropped.
+#[derive(Clone, Debug)]
+pub struct FixedDecimal {
+    count: isize,
+    exponent: isize,
+    is_negative: bool,
+    is_truncated: bool, // non zero digits were dropped
+    digits: [u8; FIXED_DECIMAL_DIGITS],
+}
+
+impl FixedDecimal {
+    /// Read decimal number with optional sign, point and exponent from the start of text.
+    ///
+    /// Inf and NaN are not accepted. On success returns the decimal and count of bytes read.
+    pub fn parse(text: &[u8]) -> Option<(FixedDecimal, usize)> {
+        read_fixed_decimal(TextReader::new(text), &ParseOptions::new(), false).ok()
+    }
+
+    /// Significant digits, each in range 0..=9, without leading zeros
+    pub fn digits(&self) -> &[u8] {
+        &self.digits[..self.count as usize]
+    }
+
+    /// Decimal exponent of the first digit, `1.5` has exponent 0, `150` has exponent 2
+    pub fn exponent(&self) -> isize {
+        self.exponent
+    }
+
+    /// Sign of the number, set for `-0` too
+    pub fn is_negative(&self) -> bool {
+        self.is_negative
+    }
+
+    /// Some non zero digits were dropped, so the number is a bit larger than stored digits
+    pub fn is_truncated(&self) -> bool {
+        self.is_truncated
+    }
+
+    /// Convert to nearest Double, see [`crate`] docs for accuracy
+    pub fn to_f64(&self) -> f64 {
+        self.into()
+    }
+
+    /// Convert to nearest Single, without rounding to Double first
+    pub fn to_f32(&self) -> f32 {
+        self.into()
+    }
+}
+
+impl From<&FixedDecimal> for f64 {
+    fn from(decimal: &FixedDecimal) -> Self {
+        let number = decimal.to_double_double();
+
+        #[cfg(feature = "correct-rounding")]
+        let number = DoubleDouble::from(if decimal.is_in_range() && is_near_halfway(number) {
+            decimal.round_exact(number.hi)
+        } else {
+            number.hi
+        });
+
+        if decimal.is_negative { -number.hi } else { number.hi }
+    }
+}
+
+impl From<&FixedDecimal> for f32 {
+    fn from(decimal: &FixedDecimal) -> Self {
+        const LAST_ACCURACY_EXPONENT_10: isize = 10; // for Single
+        const MAX_SAFE_INT: f32 = 16777215.0; // (2^24−1) for Single
+        const POWER_OF_10: [f32; 1+LAST_ACCURACY_EXPONENT_10 as usize] = [
+            1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10
+        ];
+        // clip ranges, 1e39 > Single max, 1e-46 < Single min denormal / 2
+        const MAX_EXPONENT_10: isize = 38;
+        const MIN_EXPONENT_10: isize = -47;
+
+        let result = match decimal.exponent {
+        _ if decimal.count == 0 => 0.0,
+        exponent if exponent > MAX_EXPONENT_10 => f32::INFINITY,
+        exponent if exponent < MIN_EXPONENT_10 => 0.0,
+        _ => {
+            // fast path, exact mantissa and exact power of ten give correct rounding
+            let exponent = decimal.exponent - decimal.count + 1;
+            let mantissa = decimal.digits().iter().try_fold(0.0f32, |number, &digit| {
+                let number = number * 10.0 + digit as f32;
+                if number <= MAX_SAFE_INT { Some(number) } else { None }
+            });
+            match mantissa {
+            Some(number) if (0..=LAST_ACCURACY_EXPONENT_10).contains(&exponent) => {
+                number * POWER_OF_10[exponent as usize]
+            }
+            Some(number) if (-LAST_ACCURACY_EXPONENT_10..0).contains(&exponent) => {
+                number / POWER_OF_10[-exponent as usize]
+            }
+            _ => {
+                let number = decimal.to_double_double();
+                let single = double_double_to_f32(number);
+
+                #[cfg(feature = "correct-rounding")]
+                let single = if is_near_halfway_f32(number, single) {
+                    decimal.round_exact(single)
+                } else {
+                    single
+                };
+
+                single
+            }
+            }
+        }
+        };
+
+        if decimal.is_negative { -result } else { result }
+    }
+}
+
+// Round positive Double-Double to nearest Single, Hi is rounded first, so when Hi lies exactly
+// halfway between two Singles the tie is decided by Lo to avoid double rounding
+fn double_double_to_f32(number: DoubleDouble) -> f32 {
+    const SINGLE_OVERFLOW: f64 = 340282366920938463463374607431768211456.0; // 2^128
+
+    let single = number.hi as f32;
+    if number.lo == 0.0 || single as f64 == number.hi {
+        return single;
+    }
+
+    // Singles around Hi, infinity stands for 2^128
+    let (down, up) = if single as f64 > number.hi {
+        (single.next_down(), single)
+    } else {
+        (single, single.next_up())
+    };
+    let up_hi = if up.is_infinite() { SINGLE_OVERFLOW } else { up as f64 };
+
+    // tie
+    if number.hi - down as f64 == up_hi - number.hi {
+        return if number.lo > 0.0 { up } else { down };
+    }
+    single
+}
+
+impl FixedDecimal {
+    // Decimal with the last digit increased, which is above the value with any dropped digits
+    pub(crate) fn last_digit_up(&self) -> FixedDecimal {
+        let mut decimal = self.clone();
+        decimal.is_truncated = false;
+        // trailing nines turn into zeros, which are not stored
+        let mut count = decimal.digits().len();
+        while count > 0 && decimal.digits[count - 1] == 9 {
+            count -= 1;
+        }
+        if count == 0 {
+            decimal.digits[0] = 1;
+            decimal.count = 1;
+            decimal.exponent += 1;
+        } else {
+            decimal.digits[count - 1] += 1;
+            decimal.count = count as isize;
+        }
+        decimal
+    }
+
+    // Exponent is in range where the value can round to a non zero finite Double,
+    // outside of it Double-Double conversion always gives exact zero or infinity
+    pub(crate) fn is_in_range(&self) -> bool {
+        self.count != 0 && (-326..=309).contains(&self.exponent)
+    }
+
+    // Convert absolute value to Double-Double
+    pub(crate) fn to_double_double(&self) -> DoubleDouble {
+        const LAST_ACCURACY_EXPONENT_10: isize = 22; // for Double
+        const LAST_ACCURACY_POWER_10: f64 = 1e22; // for Double
+        const MAX_SAFE_INT: f64 = 9007199254740991.0; // (2^53−1) for Double
+        const MAX_SAFE_HI: f64 = (MAX_SAFE_INT - 9.) / 10.; // for X * 10 + 9
+        const POWER_OF_10: [f64; 1+LAST_ACCURACY_EXPONENT_10 as usize] = [
+            1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11,
+            1e12, 1e13, 1e14, 1e15, 1e16, 1e17, 1e18, 1e19, 1e20, 1e21, 1e22
+        ];
+
+        let digits = &self.digits()[..self.digits().len().min(DOUBLE_DOUBLE_DIGITS)];
+
+        let mut number: DoubleDouble = 0.0.into();
+        // set mantissa
+        for &digit in digits {
+            if number.hi <= MAX_SAFE_HI {
+                number.hi = number.hi * 10.0 + digit as f64;
+            } else {
+                number = number * 10.0 + digit as f64;
+            }
+        };
+
+        let mut exponent = self.exponent - digits.len() as isize + 1;
+        // positive exponent
+        while exponent > 0 {
+            if exponent > LAST_ACCURACY_EXPONENT_10 {
+                number *= LAST_ACCURACY_POWER_10; // * e22
+                // overflow break
+                if number.hi.is_infinite() {
+                    break;
+                }
+                exponent -= LAST_ACCURACY_EXPONENT_10;
+            } else {
+                number *= POWER_OF_10[exponent as usize]; // * eX
+                break;
+            }
+        }
+
+        // negative exponent
+        while exponent < 0 {
+            if exponent < -LAST_ACCURACY_EXPONENT_10 {
+                number /= LAST_ACCURACY_POWER_10; // / e22
+                // underflow break
+                if number.hi == 0.0 {
+                    break;
+                }
+                exponent += LAST_ACCURACY_EXPONENT_10;
+            } else {
+                number /= POWER_OF_10[-exponent as usize]; // / eX
+                break;
+            }
+        }
+
+        number
+    }
+}
+
+// Read decimal with grammar of Options, in strict mode a second point, a point without required
+// digits after it or an exponent without digits is an error, otherwise reading stops before them
+pub(crate) fn read_fixed_decimal<'a>(mut p: impl Reader<'a>, options: &ParseOptions, strict: bool) -> Result<(FixedDecimal, usize), ParseFloatError> {
+    const CLIP_EXPONENT: isize = 1000000;
+
+    // read sign
+    let is_negative = match p.get() {
+        b'+' if options.leading_plus => { p.advance(); false }
+        b'-' => { p.advance(); true },
+        _ => false,
+    };
+
+    let mut decimal = FixedDecimal{
+        count: 0,
+        is_truncated: false,
+        digits: [0; FIXED_DECIMAL_DIGITS],
+        exponent: -1,
+        is_negative,
+    };
+
+    // read mantissa
+    let mut has_digit = false; // has read any digit (0..9)
+    let mut has_point = false; // has read decimal point
+    let mut has_fraction_digit = false; // has read any digit after decimal point
+    let mut point_position = 0; // position of decimal point
+    let mut is_after_digit = false; // previous char is a digit
+    let separator = options.digit_separator;
+    let grouping = options.grouping_separator;
+    let mut has_group = false; // has read grouping separator
+    let mut group_digits = 0; // count of digits in the current group of integer part
+    let mut group_start = None; // decimal and reader before the last grouping separator
+    let mut bad_group = None; // position of the char breaking groups of three digits
+    'read_mantissa_loop: while !p.ended() {
+        // separator between digits
+        if is_after_digit && p.skip_digit_separator(separator) {
+            continue;
+        }
+
+        // grouping separator between digits of integer part
+        let p_group = p.clone();
+        if is_after_digit && !has_point && p.skip_digit_separator(grouping) {
+            if has_group && group_digits != 3 {
+                bad_group = Some(p_group.position());
+                break 'read_mantissa_loop;
+            }
+            // long first group is a number without grouping, reading stops before the separator
+            if group_digits > 3 {
+                bad_group = Some(p_group.position());
+            }
+            group_start = Some((decimal.count, decimal.exponent, decimal.is_truncated, p_group));
+            if bad_group.is_some() {
+                break 'read_mantissa_loop;
+            }
+            has_group = true;
+            group_digits = 0;
+            continue;
+        }
+        is_after_digit = p.get().is_ascii_digit();
+
+        match p.get() {
+        b'0'..=b'9' => {
+            // only zeros were read in integer part
+            if !options.leading_zeros && !has_point && has_digit && decimal.count == 0 {
+                return Err(ParseFloatError::InvalidCharacter(p.position()));
+            }
+            if !has_point {
+                // group has only three digits
+                if has_group && group_digits == 3 {
+                    bad_group = Some(p.position());
+                    break 'read_mantissa_loop;
+                }
+                group_digits += 1;
+            }
+            if decimal.count != 0 || p.get() != b'0' {
+                // save digit
+                if decimal.count < FIXED_DECIMAL_DIGITS as isize {
+                    decimal.digits[decimal.count as usize] = p.get() - b'0';
+                    decimal.count += 1;
+                } else if p.get() != b'0' {
+                    decimal.is_truncated = true;
+                }
+                // inc exponenta
+                if !has_point && decimal.exponent < CLIP_EXPONENT {
+                    decimal.exponent += 1;
+                }
+            } else {
+                // skip zero (dec exponenta)
+                if has_point && (decimal.exponent > -CLIP_EXPONENT) {
+                    decimal.exponent -= 1;
+                }
+            }
+            has_digit = true;
+            has_fraction_digit = has_point;
+        },
+        c if c == options.decimal_separator => {
+            if has_point {
+                if strict && !options.second_point_ends_number {
+                    return Err(ParseFloatError::InvalidCharacter(p.position()));
+                }
+                // text before the second point is a number even without digits, unlike in C
+                if options.number_before_second_point {
+                    return Ok((decimal, p.position()));
+                }
+                break 'read_mantissa_loop;
+            }
+            if options.require_digits_before_point && !has_digit {
+                return Err(ParseFloatError::InvalidCharacter(p.position()));
+            }
+            if has_group && group_digits != 3 {
+                bad_group = Some(p.position());
+                break 'read_mantissa_loop;
+            }
+            has_point = true;
+            point_position = p.position();
+        },
+        _ => {
+            break 'read_mantissa_loop;
+        },
+        }
+        p.advance();
+    }
+
+    // last group of integer part has three digits too
+    if bad_group.is_none() && has_group && !has_point && group_digits != 3 {
+        bad_group = Some(p.position());
+    }
+
+    // in strict mode bad group is an error, otherwise reading stops before the separator
+    // of the bad group
+    if let Some(position) = bad_group {
+        if strict {
+            return Err(ParseFloatError::InvalidCharacter(position));
+        }
+        if let Some((count, exponent, is_truncated, p_group)) = group_start {
+            decimal.count = count;
+            decimal.exponent = exponent;
+            decimal.is_truncated = is_truncated;
+            p = p_group;
+        }
+    }
+
+    if !has_digit {
+        if p.ended() {
+            return Err(ParseFloatError::NoDigits);
+        }
+        return Err(ParseFloatError::InvalidCharacter(p.position()));
+    }
+
+    // point without digits after it is not a part of the number
+    if options.require_digits_after_point && has_point && !has_fraction_digit {
+        if strict {
+            return Err(ParseFloatError::InvalidCharacter(point_position));
+        }
+        return Ok((decimal, point_position));
+    }
+
+    // read exponenta, implicit exponent starts with sign without exponent char
+    let has_exponent_char = !p.ended() && options.exponent_chars.contains(&p.get());
+    let has_implicit_exponent = options.implicit_exponent && matches!(p.get(), b'+' | b'-');
+    if has_exponent_char || has_implicit_exponent {
+        let p_start_exponent = p.position();
+        if has_exponent_char {
+            p.advance();
+        }
+
+        let mut exponent: isize = 0;
+        let exponent_sign: isize = match p.get() {
+            b'+' => { p.advance(); 1 },
+            b'-' => { p.advance(); -1 },
+            _ => 1,
+        };
+
+        // read
+        if p.get().is_ascii_digit() {
+            while p.get().is_ascii_digit() {
+                exponent = (exponent * 10 + (p.get() - b'0') as isize).min(CLIP_EXPONENT);
+                p.advance();
+                p.skip_digit_separator(separator);
+            }
+        } else {
+            if strict {
+                return Err(ParseFloatError::DanglingExponent(p_start_exponent));
+            }
+            return Ok((decimal, p_start_exponent));
+        }
+
+        // fix
+        decimal.exponent += exponent_sign * exponent;
+    }
+
+    Ok((decimal, p.position()))
+}
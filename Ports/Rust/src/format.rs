@@ -0,0 +1,611 @@
+//! Double to decimal text, the inverse of parsing, with exact big integer arithmetic
+
+use std::cmp::Ordering;
+
+use crate::bigint::{exact_digits, round_digits, BigInt};
+use crate::correct_rounding::BinaryFloat;
+use crate::fixed_decimal::FixedDecimal;
+
+/// Max length of [`format_f64`] result, like `-0.0000012345678901234567`
+pub const FORMAT_F64_MAX_LENGTH: usize = 25;
+
+// Small text buffer on the stack
+pub(crate) struct TextBuffer {
+    bytes: [u8; 32],
+    len: usize,
+}
+
+impl TextBuffer {
+    fn new() -> Self {
+        TextBuffer{
+            bytes: [0; 32],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    fn push(&mut self, c: u8) {
+        self.bytes[self.len] = c;
+        self.len += 1;
+    }
+
+    fn push_bytes(&mut self, text: &[u8]) {
+        for &c in text {
+            self.push(c);
+        }
+    }
+
+    fn push_repeat(&mut self, c: u8, count: usize) {
+        for _ in 0..count {
+            self.push(c);
+        }
+    }
+
+    fn push_integer(&mut self, mut value: u64) {
+        let start = self.len;
+        loop {
+            self.push(b'0' + (value % 10) as u8);
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        self.bytes[start..self.len].reverse();
+    }
+}
+
+// Multiply all by 10^Exponent
+fn mul_pow10(numbers: &mut [&mut BigInt], exponent: u32) {
+    for number in numbers {
+        number.mul_pow5(exponent);
+        number.mul_pow2(exponent);
+    }
+}
+
+// R + M is above S, or equal to it when the boundary is inclusive
+fn is_above(r: &BigInt, m: &BigInt, s: &BigInt, inclusive: bool) -> bool {
+    let mut sum = r.clone();
+    sum.add(m);
+    if inclusive { sum >= *s } else { sum > *s }
+}
+
+// Shortest digits of positive finite Value, as Digits * 10^Exponent without trailing zeros.
+// The "free-format" algorithm of Steele and White, with the last digit nearest to Value,
+// in the form of Burger and Dybvig, exactly with big integers: Value = R / S, and the
+// rounding interval of Value is (R - M- .. R + M+) / S, with boundaries inclusive for even
+// mantissa, which a correctly rounded parser reads back to Value with ties to even.
+fn shortest_digits(value: f64) -> (u64, isize) {
+    let (mantissa, exponent) = value.parts();
+    let is_even = mantissa % 2 == 0;
+    // the interval below a power of two is half as wide, except for the min normal
+    let is_unequal = mantissa == 1 << 52 && exponent > -1074;
+
+    // Value * 2 and the half distances to neighbours, all scaled to integers
+    let shift = if is_unequal { 2 } else { 1 };
+    let mut r = BigInt::from_u64(mantissa << shift);
+    let mut s = BigInt::from_u64(1 << shift);
+    let mut m_plus = BigInt::from_u64(if is_unequal { 2 } else { 1 });
+    let mut m_minus = BigInt::from_u64(1);
+    if exponent >= 0 {
+        for number in [&mut r, &mut m_plus, &mut m_minus] {
+            number.mul_pow2(exponent as u32);
+        }
+    } else {
+        s.mul_pow2(-exponent as u32);
+    }
+
+    // scale to 0.1 <= (R + M+) / S < 1, estimate is at most one too small
+    let mut k = (value.log10() - 1e-10).ceil() as isize;
+    if k >= 0 {
+        mul_pow10(&mut [&mut s], k as u32);
+    } else {
+        mul_pow10(&mut [&mut r, &mut m_plus, &mut m_minus], -k as u32);
+    }
+    while is_above(&r, &m_plus, &s, is_even) {
+        s.mul_add_small(10, 0);
+        k += 1;
+    }
+
+    // digits until the rest is inside the interval
+    let mut digits: u64 = 0;
+    let mut count = 0;
+    loop {
+        mul_pow10(&mut [&mut r, &mut m_plus, &mut m_minus], 1);
+        let mut digit = 0;
+        while r >= s {
+            r.sub(&s);
+            digit += 1;
+        }
+        count += 1;
+
+        // digit is low enough, digit + 1 is high enough
+        let is_low = if is_even { r <= m_minus } else { r < m_minus };
+        let is_high = is_above(&r, &m_plus, &s, is_even);
+        digits = digits * 10 + digit;
+        match (is_low, is_high) {
+        (false, false) => continue,
+        (true, false) => {}
+        (false, true) => digits += 1,
+        (true, true) => {
+            // nearest of both, exact ties to even
+            let mut twice = r.clone();
+            twice.mul_pow2(1);
+            match twice.cmp(&s) {
+            Ordering::Less => {}
+            Ordering::Greater => digits += 1,
+            Ordering::Equal => digits += digit % 2,
+            }
+        }
+        }
+        break;
+    }
+
+    strip_zeros(digits, k - count)
+}
+
+fn strip_zeros(mut digits: u64, mut exponent: isize) -> (u64, isize) {
+    while digits.is_multiple_of(10) {
+        digits /= 10;
+        exponent += 1;
+    }
+    (digits, exponent)
+}
+
+// Text of Digits * 10^Exponent, negative when Is_Negative
+fn write_notation(is_negative: bool, digits: u64, exponent: isize) -> TextBuffer {
+    let mut text = TextBuffer::new();
+    if is_negative {
+        text.push(b'-');
+    }
+    let mut digit_text = TextBuffer::new();
+    digit_text.push_integer(digits);
+    let digit_text = digit_text.as_bytes();
+    let count = digit_text.len() as isize;
+    // exponent of the first digit
+    let exponent = exponent + count - 1;
+
+    if (-7 < exponent) && (exponent < 21) {
+        if exponent >= count - 1 {
+            // 1500
+            text.push_bytes(digit_text);
+            text.push_repeat(b'0', (exponent - count + 1) as usize);
+        } else if exponent >= 0 {
+            // 1.5
+            text.push_bytes(&digit_text[..exponent as usize + 1]);
+            text.push(b'.');
+            text.push_bytes(&digit_text[exponent as usize + 1..]);
+        } else {
+            // 0.015
+            text.push_bytes(b"0.");
+            text.push_repeat(b'0', (-exponent - 1) as usize);
+            text.push_bytes(digit_text);
+        }
+    } else {
+        // 1.5e+21
+        text.push(digit_text[0]);
+        if count > 1 {
+            text.push(b'.');
+            text.push_bytes(&digit_text[1..]);
+        }
+        text.push(b'e');
+        text.push(if exponent < 0 { b'-' } else { b'+' });
+        text.push_integer(exponent.unsigned_abs() as u64);
+    }
+
+    text
+}
+
+// Shortest text of Value for correctly rounded parsing
+fn write_digits(value: f64) -> TextBuffer {
+    if value.is_finite() && value != 0.0 {
+        let (digits, exponent) = shortest_digits(value.abs());
+        return write_notation(value.is_sign_negative(), digits, exponent);
+    }
+
+    let mut text = TextBuffer::new();
+    if value.is_nan() {
+        text.push_bytes(b"NaN");
+        return text;
+    }
+    if value.is_sign_negative() {
+        text.push(b'-');
+    }
+    text.push_bytes(if value.is_infinite() { b"Infinity" } else { b"0" });
+    text
+}
+
+// parse_float reads the whole Text to Value
+fn is_read_back(value: f64, text: &TextBuffer) -> bool {
+    crate::parse_float(text.as_bytes()) == Some((value, text.as_bytes().len()))
+}
+
+// Absolute value of Text is inside the rounding interval of positive finite Value, so
+// a correctly rounded parser reads it to Value, boundaries are inclusive for even mantissa
+fn is_in_interval(value: f64, text: &TextBuffer) -> bool {
+    let Some((decimal, _)) = FixedDecimal::parse(text.as_bytes()) else {
+        return false;
+    };
+    let (low, high) = (decimal.compare_midpoint(value.next_down(), value), decimal.compare_midpoint(value, value.next_up()));
+    if value.is_even() {
+        low != Ordering::Less && high != Ordering::Greater
+    } else {
+        low == Ordering::Greater && high == Ordering::Less
+    }
+}
+
+// Text of Value which parse_float reads back. The shortest digits are correctly rounded, but
+// without correct rounding parse_float can read them one Double off, then correctly rounded
+// texts of 17 and 18 digits are tried, with the last digit moved by one, two... inside the
+// rounding interval of Value, until parse_float reads the text back
+pub(crate) fn write_shortest(value: f64) -> TextBuffer {
+    let text = write_digits(value);
+    if cfg!(feature = "correct-rounding") || !value.is_finite() || value == 0.0 || is_read_back(value, &text) {
+        return text;
+    }
+
+    // text of Digits * 10^Exponent, None out of the rounding interval of Value
+    let write_inside = |digits: u64, exponent: isize| {
+        let (digits, exponent) = strip_zeros(digits, exponent);
+        let text = write_notation(value.is_sign_negative(), digits, exponent);
+        is_in_interval(value.abs(), &text).then_some(text)
+    };
+    let (exact, exponent) = exact_digits(value.abs());
+    for count in 17..=18 {
+        let nearest = round_digits(&exact, count).iter().fold(0, |digits, &digit| digits * 10 + digit as u64);
+        // exponent of the last digit, the same after carry to Count + 1 digits
+        let exponent = exponent - count + 1;
+        match write_inside(nearest, exponent) {
+        Some(text) if is_read_back(value, &text) => return text,
+        _ => {}
+        }
+        // move the last digit up and down while inside the interval
+        let (mut is_up_inside, mut is_down_inside) = (true, true);
+        for step in 1.. {
+            for (is_inside, digits) in [(&mut is_up_inside, nearest + step), (&mut is_down_inside, nearest - step)] {
+                if *is_inside {
+                    match write_inside(digits, exponent) {
+                    Some(text) if is_read_back(value, &text) => return text,
+                    Some(_) => {}
+                    None => *is_inside = false,
+                    }
+                }
+            }
+            if !is_up_inside && !is_down_inside {
+                break;
+            }
+        }
+    }
+    // not found for any of millions of tested Doubles
+    text
+}
+
+/// Shortest decimal text which [`parse_float`](crate::parse_float) reads back to the same Double.
+///
+/// Among the shortest texts the nearest to the value is chosen. The notation is the same as
+/// JavaScript `Number.prototype.toString`: fixed for decimal exponents from -6 to 20,
+/// scientific otherwise, `Infinity` and `NaN` for special values, `-0` for negative zero.
+/// Exact ties of the last digit are rounded to even.
+///
+/// The digits are generated exactly with big integers, so with the `correct-rounding` feature
+/// the shortest text is always read back. Without it [`parse_float`](crate::parse_float) can be
+/// one Double off, see [`crate`] docs for accuracy, then a text of 17 or 18 digits is searched
+/// inside the rounding interval of the value until [`parse_float`](crate::parse_float) reads
+/// the same Double, so the result and the parser agree, and the text is still at most
+/// [`FORMAT_F64_MAX_LENGTH`] long.
+///
+/// ```
+/// use pure_parse_float::format_f64;
+///
+/// assert_eq!(format_f64(0.1 + 0.2), "0.30000000000000004");
+/// assert_eq!(format_f64(1e21), "1e+21");
+/// assert_eq!(format_f64(-1.5e-7), "-1.5e-7");
+/// ```
+pub fn format_f64(value: f64) -> String {
+    String::from_utf8(write_shortest(value).as_bytes().to_vec()).unwrap()
+}
+
+// Max digits after the point of exact decimal of Double, 2^-1074 has 1074 digits
+const MAX_FIXED_DECIMALS: usize = 1074;
+// Max significant digits of exact decimal of Double, for the max subnormal
+const MAX_SIGNIFICANT_DIGITS: usize = 767;
+
+// Text of Head, Zeros zero digits and Tail, digits past the exact decimal of a Double are
+// always zeros, so they are counted instead of stored
+pub(crate) struct PaddedText {
+    head: String,
+    zeros: usize,
+    tail: String,
+}
+
+impl PaddedText {
+    fn new(head: String) -> Self {
+        PaddedText{
+            head,
+            zeros: 0,
+            tail: String::new(),
+        }
+    }
+
+    // None when the length does not fit usize
+    pub(crate) fn len(&self) -> Option<usize> {
+        self.head.len().checked_add(self.zeros)?.checked_add(self.tail.len())
+    }
+
+    pub(crate) fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.head.bytes()
+            .chain(std::iter::repeat_n(b'0', self.zeros))
+            .chain(self.tail.bytes())
+    }
+
+    fn into_string(self) -> String {
+        let mut text = self.head;
+        text.extend(std::iter::repeat_n('0', self.zeros));
+        text.push_str(&self.tail);
+        text
+    }
+}
+
+// Inf and NaN, with sign like printf
+fn format_special(value: f64) -> Option<String> {
+    let sign = if value.is_sign_negative() { "-" } else { "" };
+    if value.is_nan() {
+        return Some(format!("{sign}nan"));
+    }
+    if value.is_infinite() {
+        return Some(format!("{sign}inf"));
+    }
+    None
+}
+
+pub(crate) fn write_fixed(value: f64, decimals: usize) -> PaddedText {
+    if let Some(text) = format_special(value) {
+        return PaddedText::new(text);
+    }
+
+    let mut text = String::new();
+    if value.is_sign_negative() {
+        text.push('-');
+    }
+
+    // integer Value * 10^Decimals, without the zeros past the exact decimal
+    let padding = decimals.saturating_sub(MAX_FIXED_DECIMALS);
+    let decimals = decimals - padding;
+    let number = if value == 0.0 {
+        Vec::new()
+    } else {
+        let (digits, exponent) = exact_digits(value.abs());
+        round_digits(&digits, exponent + decimals as isize + 1)
+    };
+
+    // at least one digit before point
+    let zeros = (decimals + 1).saturating_sub(number.len());
+    let digits: Vec<u8> = std::iter::repeat_n(0, zeros).chain(number).collect();
+    let point = digits.len() - decimals;
+    text.extend(digits[..point].iter().map(|&digit| (b'0' + digit) as char));
+    if decimals > 0 {
+        text.push('.');
+        text.extend(digits[point..].iter().map(|&digit| (b'0' + digit) as char));
+    }
+    PaddedText{
+        head: text,
+        zeros: padding,
+        tail: String::new(),
+    }
+}
+
+/// Value with exactly Decimals digits after the point, correctly rounded with ties to even.
+///
+/// Same text as C `printf("%.*f", decimals, value)` of glibc, which is read back by
+/// [`parse_float`](crate::parse_float): `inf`, `nan` and sign for negative values and `-0`.
+/// Digits after the 1074th decimal are always zeros, they are not computed.
+///
+/// ```
+/// use pure_parse_float::format_fixed;
+///
+/// assert_eq!(format_fixed(2.5, 0), "2");
+/// assert_eq!(format_fixed(-0.125, 2), "-0.12");
+/// assert_eq!(format_fixed(0.1, 20), "0.10000000000000000555");
+/// assert_eq!(format_fixed(1e21, 1), "1000000000000000000000.0");
+/// ```
+pub fn format_fixed(value: f64, decimals: usize) -> String {
+    write_fixed(value, decimals).into_string()
+}
+
+pub(crate) fn write_scientific(value: f64, significant_digits: usize) -> PaddedText {
+    if let Some(text) = format_special(value) {
+        return PaddedText::new(text);
+    }
+
+    // digits past the exact decimal are zeros
+    let count = significant_digits.max(1);
+    let padding = count.saturating_sub(MAX_SIGNIFICANT_DIGITS);
+    let count = count - padding;
+    let mut text = String::new();
+    if value.is_sign_negative() {
+        text.push('-');
+    }
+
+    let (mut digits, mut exponent) = if value == 0.0 {
+        (vec![0; count], 0)
+    } else {
+        let (digits, exponent) = exact_digits(value.abs());
+        (round_digits(&digits, count as isize), exponent)
+    };
+    // carry to the next power of ten
+    if digits.len() > count {
+        digits.pop();
+        exponent += 1;
+    }
+
+    text.push((b'0' + digits[0]) as char);
+    if count > 1 {
+        text.push('.');
+        text.extend(digits[1..].iter().map(|&digit| (b'0' + digit) as char));
+    }
+    PaddedText{
+        head: text,
+        zeros: padding,
+        tail: format!("e{}{:02}", if exponent < 0 { '-' } else { '+' }, exponent.unsigned_abs()),
+    }
+}
+
+/// Value in scientific notation `d.ddde+XX` with SignificantDigits digits (at least one),
+/// correctly rounded with ties to even.
+///
+/// Same text as C `printf("%.*e", significant_digits - 1, value)` of glibc, which is read back
+/// by [`parse_float`](crate::parse_float): exponent has at least two digits,
+/// `inf`, `nan` and sign for negative values and `-0`.
+/// Digits after the 767th significant digit are always zeros, they are not computed.
+///
+/// ```
+/// use pure_parse_float::format_scientific;
+///
+/// assert_eq!(format_scientific(1234.5, 3), "1.23e+03");
+/// assert_eq!(format_scientific(9.96, 2), "1.0e+01");
+/// assert_eq!(format_scientific(-5e-324, 1), "-5e-324");
+/// assert_eq!(format_scientific(0.0, 4), "0.000e+00");
+/// ```
+pub fn format_scientific(value: f64, significant_digits: usize) -> String {
+    write_scientific(value, significant_digits).into_string()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(unix, target_env = "gnu"))]
+    use std::ffi::{c_char, c_int, CStr, CString};
+    use super::{format_f64, format_fixed, format_scientific, shortest_digits, write_digits, FORMAT_F64_MAX_LENGTH};
+    use crate::parse_float;
+    use crate::random::random_generator;
+
+    #[cfg(all(unix, target_env = "gnu"))]
+    extern "C" {
+        fn snprintf(buffer: *mut c_char, size: usize, format: *const c_char, ...) -> c_int;
+    }
+
+    // glibc printf with one precision and one double argument
+    #[cfg(all(unix, target_env = "gnu"))]
+    fn printf(format: &str, precision: usize, value: f64) -> String {
+        let format = CString::new(format).unwrap();
+        let mut buffer = vec![0 as c_char; 2000];
+        unsafe {
+            snprintf(buffer.as_mut_ptr(), buffer.len(), format.as_ptr(), precision as c_int, value);
+            CStr::from_ptr(buffer.as_ptr()).to_str().unwrap().to_string()
+        }
+    }
+
+    #[test]
+    fn notation() {
+        assert_eq!(format_f64(0.0), "0");
+        assert_eq!(format_f64(-0.0), "-0");
+        assert_eq!(format_f64(f64::NAN), "NaN");
+        assert_eq!(format_f64(f64::NEG_INFINITY), "-Infinity");
+        assert_eq!(format_f64(1.0), "1");
+        assert_eq!(format_f64(-1500.0), "-1500");
+        assert_eq!(format_f64(123.456), "123.456");
+        assert_eq!(format_f64(0.000001), "0.000001");
+        assert_eq!(format_f64(1e-7), "1e-7");
+        assert_eq!(format_f64(1e20), "100000000000000000000");
+        assert_eq!(format_f64(1.5e300), "1.5e+300");
+        assert_eq!(format_f64(5e-324), "5e-324");
+        assert_eq!(format_f64(9007199254740993.0), "9007199254740992");
+        assert_eq!(format_f64(-1.2345678901234567e-7), "-1.2345678901234566e-7");
+        assert_eq!(format_f64(-0.0000012345678901234567).len(), FORMAT_F64_MAX_LENGTH);
+    }
+
+    #[test]
+    fn shortest() {
+        // powers of two have a narrower interval below, except the min normal
+        for (value, text) in [
+            (f64::MIN_POSITIVE.next_down(), "2.225073858507201e-308"), (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+            (5e-324, "5e-324"), (1e-323, "1e-323"), (1.5e-323, "1.5e-323"), (f64::MAX, "1.7976931348623157e+308"),
+            (2f64.powi(1023), "8.98846567431158e+307"), (2f64.powi(-1021), "4.450147717014403e-308"),
+            (2f64.powi(-1023), "1.1125369292536007e-308"), (2f64.powi(63), "9223372036854776000"),
+            (1e23, "1e+23"), (0.3, "0.3"), (1e21, "1e+21"), (1e-7, "1e-7"),
+            // exact ties of the last digit, 2^-25 = 2.98023223876953125e-8
+            (2f64.powi(-25), "2.9802322387695312e-8"),
+        ] {
+            assert_eq!(write_digits(value).as_bytes(), text.as_bytes(), "shortest digits of {value:e}");
+            let res = format_f64(value);
+            assert_eq!(parse_float(res.as_bytes()), Some((value, res.len())), "read back of {res} for {value:e}");
+            if cfg!(feature = "correct-rounding") {
+                assert_eq!(res, text, "text of {value:e}");
+            }
+        }
+        // parse_float gives infinity for the shortest text without correct rounding
+        let res = format_f64(f64::MAX);
+        assert_eq!(parse_float(res.as_bytes()), Some((f64::MAX, res.len())), "read back of {res}");
+        let res = format_f64(-2.364289300379599e-307);
+        assert_eq!(parse_float(res.as_bytes()), Some((-2.364289300379599e-307, res.len())), "read back of {res}");
+
+        let mut next = random_generator(0x6A09E667F3BCC909);
+        for _ in 0..10_000 {
+            let value = f64::from_bits(match next() % 4 {
+            0 => next() % 0x000F_FFFF_FFFF_FFFF, // denormals
+            1 => (next() % 0x7FF) << 52, // powers of two
+            _ => next() % 0x7FF0_0000_0000_0000,
+            }).max(5e-324);
+
+            // same digits as std, which is shortest and nearest, but rounds exact ties up
+            let (digits, exponent) = shortest_digits(value);
+            let expected = format!("{value:e}");
+            let (mantissa, _) = expected.split_once('e').unwrap();
+            let mantissa: u64 = mantissa.replace('.', "").parse().unwrap();
+            assert!(digits == mantissa || digits + 1 == mantissa && digits % 2 == 0, "nearest digits of {value:e}: {digits}");
+            assert_eq!(format!("{digits}e{exponent}").parse::<f64>(), Ok(value), "shortest digits of {value:e}");
+
+            let res = format_f64(value);
+            assert_eq!(parse_float(res.as_bytes()), Some((value, res.len())), "read back of {res} for {value:e}");
+            assert!(res.len() <= FORMAT_F64_MAX_LENGTH, "length of {res}");
+        }
+    }
+
+    #[test]
+    #[cfg(all(unix, target_env = "gnu"))]
+    fn same_as_printf() {
+        // halfway values, subnormal edge, max Double and overflow to the next digit
+        let values = [
+            0.0, -0.0, 0.5, 1.5, 2.5, -0.125, 0.375, 9.5, 99.5, 0.05, 5e-324, f64::MIN_POSITIVE.next_down(),
+            f64::MIN_POSITIVE, f64::MAX, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, -f64::NAN, 1e22, 1e23,
+            123456789.125, 0.9999999999999999,
+        ];
+        let mut inputs: Vec<(f64, usize)> = values.iter().flat_map(|&value| [0, 1, 2, 3, 6, 17, 40, 1100].map(|precision| (value, precision))).collect();
+        let mut next = random_generator(0xBB67AE8584CAA73B);
+        for _ in 0..4_000 {
+            let value = f64::from_bits(match next() % 4 {
+            0 => next() % 0x000F_FFFF_FFFF_FFFF, // denormals
+            1 => (next() % 0x7FF) << 52 | 1 << 51, // halfway values
+            2 => next() % 0x4400_0000_0000_0000 + 0x3B00_0000_0000_0000, // near one
+            _ => next(),
+            });
+            inputs.push((value, (next() % if next().is_multiple_of(8) { 1200 } else { 40 }) as usize));
+        }
+
+        for (value, precision) in inputs {
+            assert_eq!(format_fixed(value, precision), printf("%.*f", precision, value), "%.{precision}f of {value:e}");
+            let scientific = format_scientific(value, precision + 1);
+            assert_eq!(scientific, printf("%.*e", precision, value), "%.{precision}e of {value:e}");
+            if precision >= 17 && !value.is_nan() {
+                assert_eq!(parse_float(scientific.as_bytes()), Some((value, scientific.len())), "read back of %.{precision}e of {value:e}");
+            }
+        }
+        assert_eq!(printf("%.*e", 799, f64::MIN_POSITIVE.next_down()), format_scientific(f64::MIN_POSITIVE.next_down(), 800));
+        assert_eq!(printf("%.*f", 1100, -5e-324), format_fixed(-5e-324, 1100));
+    }
+
+    #[test]
+    fn large_precision() {
+        assert_eq!(format_scientific(1.0, 0), "1e+00");
+
+        // digits past the exact decimal are zeros, only they are repeated for large precisions
+        let tiny = format_fixed(-5e-324, 1074);
+        assert!(tiny.starts_with("-0.000") && tiny.ends_with("625"));
+        assert_eq!(format_fixed(-5e-324, 1100), format!("{tiny}{}", "0".repeat(26)));
+        let max_subnormal = format_scientific(f64::MIN_POSITIVE.next_down(), 767);
+        assert!(max_subnormal.ends_with("375e-308"));
+        let (mantissa, exponent) = max_subnormal.split_once('e').unwrap();
+        assert_eq!(format_scientific(f64::MIN_POSITIVE.next_down(), 800), format!("{mantissa}{}e{exponent}", "0".repeat(33)));
+    }
+}
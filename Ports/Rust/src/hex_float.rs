@@ -0,0 +1,265 @@
+//! C99 hexadecimal floats like `0x1.921fb54442d18p+1`, converted exactly
+
+use crate::error::ParseFloatError;
+use crate::options::ParseOptions;
+use crate::reader::Reader;
+
+// Number Mantissa * 2^Exponent, as read from text
+pub(crate) struct HexFloat {
+    mantissa: u64,
+    exponent: isize,
+    is_negative: bool,
+    is_truncated: bool, // non zero bits were dropped below Mantissa
+}
+
+impl HexFloat {
+    pub(crate) fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    // Round to nearest float with Precision bits of mantissa and MinExponent as exponent of
+    // the lowest denormal bit, result is exact in Double
+    fn round(&self, precision: u32, min_exponent: isize) -> f64 {
+        if self.mantissa == 0 {
+            return 0.0;
+        }
+
+        let bits = 64 - self.mantissa.leading_zeros() as isize;
+        let lowest = (self.exponent + bits - precision as isize).max(min_exponent);
+        let shift = lowest - self.exponent;
+
+        let mut mantissa = if shift <= 0 {
+            self.mantissa << -shift
+        } else if shift > 64 {
+            0
+        } else {
+            // round half to even, dropped bits below half are sticky
+            let kept = if shift == 64 { 0 } else { self.mantissa >> shift };
+            let half = 1u64 << (shift - 1);
+            let dropped = self.mantissa & (half | (half - 1));
+            let above_half = dropped > half || dropped == half && (self.is_truncated || kept & 1 == 1);
+            kept + above_half as u64
+        };
+
+        // rounding carry to the next binade
+        let mut lowest = lowest;
+        if mantissa >> precision != 0 {
+            mantissa >>= 1;
+            lowest += 1;
+        }
+
+        mantissa as f64 * power_of_2(lowest)
+    }
+
+    // Exact value is tiny after rounding, see FixedDecimal::is_tiny, and bits below the min
+    // Double are dropped
+    #[cfg(feature = "correct-rounding")]
+    pub(crate) fn is_tiny_inexact(&self) -> bool {
+        if self.mantissa == 0 {
+            return false;
+        }
+        let bits = 64 - self.mantissa.leading_zeros() as isize;
+        let shift = -1074 - self.exponent;
+        let is_inexact = self.is_truncated || match shift {
+        ..=0 => false,
+        1..=63 => self.mantissa & ((1 << shift) - 1) != 0,
+        _ => true,
+        };
+        // in [2^-1023, 2^-1022) only values from 2^-1022 - 2^-1076 up round to 2^-1022,
+        // their first 54 bits are ones, mantissa has at most 61 bits
+        let is_tiny = match self.exponent + bits - 1 {
+        ..=-1024 => true,
+        -1023 => {
+            let shift = 54 - bits;
+            let top = if shift >= 0 { self.mantissa << shift } else { self.mantissa >> -shift };
+            top != (1 << 54) - 1
+        }
+        _ => false,
+        };
+        is_tiny && is_inexact
+    }
+
+    pub(crate) fn to_f64(&self) -> f64 {
+        let res = self.round(53, -1074);
+        if self.is_negative { -res } else { res }
+    }
+
+    pub(crate) fn to_f32(&self) -> f32 {
+        let res = self.round(24, -149) as f32;
+        if self.is_negative { -res } else { res }
+    }
+}
+
+// Exact 2^Exponent, infinity above Double range
+fn power_of_2(exponent: isize) -> f64 {
+    match exponent {
+    1024.. => f64::INFINITY,
+    -1022..=1023 => f64::from_bits(((exponent + 1023) as u64) << 52),
+    -1074..=-1023 => f64::from_bits(1 << (exponent + 1074)),
+    _ => 0.0,
+    }
+}
+
+// Read hex float with optional sign, point and binary exponent (P/p), mantissa needs at least
+// one hex digit after the 0x/0X prefix. In strict mode a second point or an exponent without
+// digits is an error, otherwise reading stops before them.
+pub(crate) fn read_hex_float<'a>(mut p: impl Reader<'a>, options: &ParseOptions, strict: bool) -> Option<Result<(HexFloat, usize), ParseFloatError>> {
+    const CLIP_EXPONENT: isize = 1000000;
+    const MAX_MANTISSA: u64 = 1 << 60; // room for one more hex digit
+
+    // read sign
+    let is_negative = match p.get() {
+        b'+' if options.leading_plus => { p.advance(); false }
+        b'-' => { p.advance(); true },
+        _ => false,
+    };
+
+    // read prefix
+    if p.get_ascii() != b'0' {
+        return None;
+    }
+    p.advance();
+    if !matches!(p.get_ascii(), b'x' | b'X') {
+        return None;
+    }
+    p.advance();
+
+    let mut hex = HexFloat{
+        mantissa: 0,
+        exponent: 0,
+        is_negative,
+        is_truncated: false,
+    };
+
+    // read mantissa
+    let mut has_digit = false; // has read any hex digit
+    let mut has_point = false; // has read point
+    loop {
+        match p.get_ascii() {
+        c if c.is_ascii_hexdigit() => {
+            let digit = (c as char).to_digit(16).unwrap() as u64;
+            if hex.mantissa < MAX_MANTISSA {
+                hex.mantissa = hex.mantissa * 16 + digit;
+                if has_point {
+                    hex.exponent -= 4;
+                }
+            } else {
+                hex.is_truncated |= digit != 0;
+                if !has_point {
+                    hex.exponent += 4;
+                }
+            }
+            has_digit = true;
+        }
+        c if c == options.decimal_separator && !has_point => has_point = true,
+        c if c == options.decimal_separator && strict && has_digit => return Some(Err(ParseFloatError::InvalidCharacter(p.position()))),
+        _ => break,
+        }
+        p.advance();
+    }
+
+    // "0x" without digits is read as decimal zero
+    if !has_digit {
+        return None;
+    }
+
+    // read binary exponent
+    if matches!(p.get_ascii(), b'p' | b'P') {
+        let p_start_exponent = p.position();
+        p.advance();
+
+        let mut exponent: isize = 0;
+        let exponent_sign: isize = match p.get_ascii() {
+            b'+' => { p.advance(); 1 },
+            b'-' => { p.advance(); -1 },
+            _ => 1,
+        };
+
+        if p.get_ascii().is_ascii_digit() {
+            while p.get_ascii().is_ascii_digit() {
+                exponent = (exponent * 10 + (p.get_ascii() - b'0') as isize).min(CLIP_EXPONENT);
+                p.advance();
+            }
+        } else {
+            if strict {
+                return Some(Err(ParseFloatError::DanglingExponent(p_start_exponent)));
+            }
+            return Some(Ok((hex, p_start_exponent)));
+        }
+
+        hex.exponent += exponent_sign * exponent;
+    }
+
+    Some(Ok((hex, p.position())))
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(unix, target_env = "gnu"))]
+    use std::ffi::{c_char, CString};
+    use crate::{parse_float_with, ParseOptions};
+    #[cfg(all(unix, target_env = "gnu"))]
+    use crate::random::random_generator;
+
+    #[cfg(all(unix, target_env = "gnu"))]
+    extern "C" {
+        fn strtod(text: *const c_char, text_end: *mut *mut c_char) -> f64;
+    }
+
+    fn parse_hex(text: &str) -> Option<(f64, usize)> {
+        parse_float_with(text.as_bytes(), &ParseOptions::new().hex_floats(true))
+    }
+
+    #[test]
+    fn hex_floats() {
+        assert_eq!(parse_hex("0x1.921fb54442d18p+1"), Some((std::f64::consts::PI, 20)));
+        assert_eq!(parse_hex("-0X.8P1"), Some((-1.0, 7)));
+        assert_eq!(parse_hex("0xff"), Some((255.0, 4)));
+        assert_eq!(parse_hex("0x1p-1074"), Some((5e-324, 9)));
+        assert_eq!(parse_hex("0x1p-1075"), Some((0.0, 9)));
+        assert_eq!(parse_hex("0x1.8p-1075"), Some((5e-324, 11)));
+        assert_eq!(parse_hex("0x1.fffffffffffff8p0"), Some((2.0, 20)));
+        assert_eq!(parse_hex("0x1.fffffffffffff7ffffffffffp0"), Some((2.0f64.next_down(), 30)));
+        assert_eq!(parse_hex("0x1.fffffffffffff80000000001p1023"), Some((f64::INFINITY, 33)));
+        assert_eq!(parse_hex("0x1p"), Some((1.0, 3)));
+        assert_eq!(parse_hex("0x"), Some((0.0, 1)));
+        assert_eq!(parse_hex("0x.p1"), Some((0.0, 1)));
+        assert_eq!(parse_float_with(b"0x1p3", &ParseOptions::new()), Some((0.0, 1)));
+    }
+
+    #[test]
+    #[cfg(all(unix, target_env = "gnu"))]
+    fn same_as_strtod() {
+        // halfway between Doubles, around the min normal and the min subnormal, max Double and overflow
+        let mut texts: Vec<String> = [
+            "0x1.00000000000008p0", "0x1.00000000000018p0", "0x1.000000000000080000001p0", "0x1.00000000000007ffp0",
+            "0x1.fffffffffffffp-1023", "0x1.fffffffffffff8p-1023", "0x0.fffffffffffff8p-1022", "0x1p-1022",
+            "0x1p-1074", "0x1p-1075", "0x1.0000000000001p-1075", "0x1.8p-1074", "0x1p-1076", "-0x1.8p-1075",
+            "0x1.fffffffffffffp1023", "0x1.fffffffffffff7ffp1023", "0x1.fffffffffffff8p1023", "0x1p1024", "-0x1p1024",
+        ].map(String::from).to_vec();
+        let mut next = random_generator(0x4F1BBCDCBFA53E0B);
+        for _ in 0..10_000 {
+            let digits = 1 + (next() % 20) as usize;
+            let point = (next() % (digits as u64 + 1)) as usize;
+            let mut text = String::from("0x");
+            for i in 0..digits {
+                if i == point {
+                    text.push('.');
+                }
+                text.push(b"0123456789abcdefABCDEF8000"[(next() % 26) as usize] as char);
+            }
+            text.push_str(&format!("p{}", (next() % 2300) as i64 - 1150));
+            texts.push(text);
+        }
+
+        for text in texts {
+            let c_text = CString::new(text.clone()).unwrap();
+            let mut c_end: *mut c_char = std::ptr::null_mut();
+            let expected = unsafe { strtod(c_text.as_ptr(), &mut c_end) };
+            let expected_count = c_end as usize - c_text.as_ptr() as usize;
+            let (value, count) = parse_hex(&text).unwrap();
+            assert_eq!(value.to_bits(), expected.to_bits(), "value of {text}: {value:e} instead of {expected:e}");
+            assert_eq!(count, expected_count, "count of {text}");
+        }
+    }
+}
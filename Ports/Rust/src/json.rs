@@ -0,0 +1,72 @@
+//! JSON number grammar of RFC 8259
+
+use crate::error::ParseFloatError;
+use crate::options::ParseOptions;
+use crate::reader::TextReader;
+use crate::read_number;
+
+/// Parse JSON number of RFC 8259 from the start of bytes, for use in JSON tokenizers.
+///
+/// The grammar is [`ParseOptions::json`] in strict mode. On success returns the value and
+/// count of bytes of the number, checking what follows the number is left to the tokenizer.
+/// Leading `+`, leading zeros, `NaN`, `Infinity` and point without digits around it are
+/// [`InvalidCharacter`](ParseFloatError::InvalidCharacter) errors, exponent without digits is
+/// a [`DanglingExponent`](ParseFloatError::DanglingExponent) error, like in
+/// [`parse_float_exact_with`](crate::parse_float_exact_with).
+/// Numbers out of Double range give infinity or zero, like [`parse_float`](crate::parse_float).
+///
+/// ```
+/// use pure_parse_float::{parse_json_number, ParseFloatError};
+///
+/// assert_eq!(parse_json_number(b"-12.5e3,"), Ok((-12.5e3, 7)));
+/// assert_eq!(parse_json_number(b"012"), Err(ParseFloatError::InvalidCharacter(1)));
+/// assert_eq!(parse_json_number(b"1."), Err(ParseFloatError::InvalidCharacter(1)));
+/// ```
+pub fn parse_json_number(text: &[u8]) -> Result<(f64, usize), ParseFloatError> {
+    let (number, count) = read_number(TextReader::new(text), &ParseOptions::json(), true)?;
+    Ok((number.to_f64(), count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_json_number;
+    use crate::ParseFloatError;
+
+    #[test]
+    fn valid_numbers() {
+        for (text, expected) in [
+            ("0", 0.0), ("-0", -0.0), ("123", 123.0), ("-123", -123.0), ("0.5", 0.5),
+            ("1.25e2", 125.0), ("1E2", 100.0), ("1e+2", 100.0), ("1e-2", 0.01), ("0e0", 0.0),
+            ("-0.0e-0", -0.0), ("1e007", 1e7), ("123456789012345678901234567890", 1.2345678901234568e29),
+            ("1e400", f64::INFINITY), ("-1e-400", -0.0),
+        ] {
+            let (value, count) = parse_json_number(text.as_bytes()).unwrap();
+            assert_eq!((value.to_bits(), count), (expected.to_bits(), text.len()), "{text}");
+        }
+    }
+
+    #[test]
+    fn number_end() {
+        assert_eq!(parse_json_number(b"1]"), Ok((1.0, 1)));
+        assert_eq!(parse_json_number(b"-2.5 "), Ok((-2.5, 4)));
+        assert_eq!(parse_json_number(b"1.5.3"), Ok((1.5, 3)));
+        assert_eq!(parse_json_number(b"1e5e5"), Ok((1e5, 3)));
+        assert_eq!(parse_json_number(b"0x1"), Ok((0.0, 1)));
+    }
+
+    #[test]
+    fn invalid_numbers() {
+        for (text, position) in [
+            ("+1", 0), (".5", 0), ("-.5", 1), ("- 1", 1), ("01", 1), ("-012", 2), ("00", 1),
+            ("1.", 1), ("1.e5", 1), ("1.x", 1), ("-0.", 2), ("NaN", 0), ("Infinity", 0),
+            ("-Infinity", 1), ("inf", 0), (" 1", 0), ("\u{FF11}", 0),
+        ] {
+            assert_eq!(parse_json_number(text.as_bytes()), Err(ParseFloatError::InvalidCharacter(position)), "{text}");
+        }
+        for (text, position) in [("1e", 1), ("1E+", 1), ("1e-x", 1), ("0.5e", 3)] {
+            assert_eq!(parse_json_number(text.as_bytes()), Err(ParseFloatError::DanglingExponent(position)), "{text}");
+        }
+        assert_eq!(parse_json_number(b""), Err(ParseFloatError::Empty));
+        assert_eq!(parse_json_number(b"-"), Err(ParseFloatError::NoDigits));
+    }
+}
@@ -1,207 +1,55 @@
-use std::ffi::{c_int, c_char, c_double};
-
-// Safe wrapper for null terminated string
-#[derive(Clone)]
-struct Reader(*const u8, usize);
-
-impl Reader {
-    fn from_raw_ptr(text: *const u8) -> Self {
-        Reader(text, 0)
-    }
-
-    #[allow(dead_code)]
-    fn from_str(text: &str) -> Self {
-        Reader(text.as_ptr(), 0)
-    }
-
-    fn get(&self) -> u8 {
-        unsafe { *self.0.add(self.1) }
-    }
-
-    // before using, make sure Reader is not ended
-    fn advance(&mut self) {
-        self.1 += 1;
-    }
-
-    fn ended(&self) -> bool {
-        self.get() == 0
-    }
-}
-
-// 31 digits garantee, with (exp^10 >= -291) or (exp^2 >= -968)
-struct DoubleDouble {
-    hi: f64,
-    lo: f64,
-}
-
-impl Into<DoubleDouble> for f64 {
-    fn into(self) -> DoubleDouble {
-        DoubleDouble{
-            hi: self,
-            lo: 0.0,
-        }
-    }
-}
-
-impl std::ops::Add<f64> for DoubleDouble {
-    type Output = Self;
-
-    // The DWPlusFP algorithm [1]
-    fn add(self, rhs: f64) -> Self::Output {
-        let result = Self::add(self.hi, rhs);
-        Self::fast_add(result.hi, result.lo + self.lo)
-    }
-}
-
-impl std::ops::Mul<f64> for &DoubleDouble {
-    type Output = DoubleDouble;
-
-    // The "DWTimesFP1" algorithm [1]
-    fn mul(self, rhs: f64) -> Self::Output {
-        let c = DoubleDouble::mul(self.hi, rhs);
-
-        let result = DoubleDouble::fast_add(c.hi, self.lo * rhs);
-        DoubleDouble::fast_add(result.hi, result.lo + c.lo)
-    }
-}
-
-impl std::ops::MulAssign<f64> for DoubleDouble {
-    fn mul_assign(&mut self, rhs: f64) {
-        *self = &*self * rhs;
-    }
-}
-
-impl std::ops::Div<f64> for &DoubleDouble {
-    type Output = DoubleDouble;
-
-    // The "DWDivFP2" algorithm [1]
-    fn div(self, rhs: f64) -> Self::Output {
-        let hi = self.hi / rhs;
-
-        let p = DoubleDouble::mul(hi, rhs);
-
-        let dhi = self.hi - p.hi;
-        let d = DoubleDouble{
-            hi: dhi,
-            lo: dhi - p.lo,
-        };
-
-        let result = DoubleDouble{
-            hi,
-            lo: (d.lo + self.lo) / rhs,
-        };
-
-        return DoubleDouble::fast_add(result.hi, result.lo);
-    }
-}
-
-impl std::ops::DivAssign<f64> for DoubleDouble {
-    fn div_assign(&mut self, rhs: f64) {
-        *self = &*self / rhs;
-    }
-}
-
-impl DoubleDouble {
-    // Add two f64 values, condition: |A| >= |B|
-    // The "Fast2Sum" algorithm (Dekker 1971) [1]
-    fn fast_add(a: f64, b: f64) -> Self {
-        let hi = a + b;
-
-        Self{
-            hi,
-            lo: b - (hi - a),
-        }
-    }
-
-    // The "2Sum" algorithm [1]
-    fn add(a: f64, b: f64) -> Self {
-        let hi = a + b;
-
-        let ah = hi - b;
-        let bh = hi - ah;
-
-        Self{
-            hi,
-            lo: (a - ah) + (b - bh),
-        }
-    }
-
-    // Multiply two f64 values
-    // The "TWO-PRODUCT" algorithm [5]
-    fn mul(a: f64, b: f64) -> Self {
-        let hi = a * b;
-
-        Self{
-            hi,
-            lo: a.mul_add(b, -hi),
-        }
-    }
-}
-
-// ---
-
-const FIXED_DECIMAL_DIGITS: usize = 17 * 2;
-struct FixedDecimal {
-    count: isize,
-    exponent: isize,
-    is_negative: bool,
-    digits: [u8; FIXED_DECIMAL_DIGITS], // Max digits in Double value * 2
-}
-
-impl Into<f64> for &FixedDecimal {
-    fn into(self) -> f64 {
-        const LAST_ACCURACY_EXPONENT_10: isize = 22; // for Double
-        const LAST_ACCURACY_POWER_10: f64 = 1e22; // for Double
-        const MAX_SAFE_INT: f64 = 9007199254740991.0; // (2^53−1) for Double
-        const MAX_SAFE_HI: f64 = (MAX_SAFE_INT - 9.) / 10.; // for X * 10 + 9
-        const POWER_OF_10: [f64; 1+LAST_ACCURACY_EXPONENT_10 as usize] = [
-            1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11,
-            1e12, 1e13, 1e14, 1e15, 1e16, 1e17, 1e18, 1e19, 1e20, 1e21, 1e22
-        ];
-
-        let mut number: DoubleDouble = 0.0.into();
-        // set mantissa
-        for &digit in &self.digits[..self.count as usize] {
-            if number.hi <= MAX_SAFE_HI {
-                number.hi = number.hi * 10.0 + digit as f64;
-            } else {
-                number = &number * 10.0 + digit as f64;
-            }
-        };
-
-        let mut exponent = self.exponent - self.count + 1;
-        match exponent {
-        _ if exponent > 0 => {
-            while exponent > LAST_ACCURACY_EXPONENT_10 {
-                number *= LAST_ACCURACY_POWER_10; // * e22
-                // overflow break
-                if number.hi.is_infinite() {
-                    break;
-                }
-                exponent -= LAST_ACCURACY_EXPONENT_10;
-            }
-            number *= POWER_OF_10[exponent as usize]; // * eX
-        }
-        _ if exponent < 0 => {
-            while exponent < -LAST_ACCURACY_EXPONENT_10 {
-                number /= LAST_ACCURACY_POWER_10; // / e22
-                // underflow break
-                if number.hi == 0.0 {
-                    break;
-                }
-                exponent += LAST_ACCURACY_EXPONENT_10;
-            }
-            number /= POWER_OF_10[-exponent as usize]; // / eX
-        }
-        _ => {}
-        }
-
-        if self.is_negative { -number.hi } else { number.hi }
-    }
-}
-
-fn read_inf_or_nan(mut p: Reader) -> Option<(f64, usize)> {
-    fn common_prefix_length(mut text: Reader, prefix: &[u8]) -> usize {
+//! PureParseFloat - This is a simple and clear, "clean" algorithm and implementation of a function
+//! for converting a string to a double number, with OK accuracy.
+//!
+//! Accurate conversion of numbers with a mantissa up to 31 digits, in the exponent range from
+//! -291 to +308, otherwise the conversion can have a maximum error of 1 ULP.
+//! With the `correct-rounding` feature, on by default, all conversions are correctly rounded.
+//!
+//! ```
+//! use pure_parse_float::{parse_float, parse_float_str};
+//!
+//! assert_eq!(parse_float(b"1.5e3 abc"), Some((1500.0, 5)));
+//! assert_eq!(parse_float_str("-inf"), Some((f64::NEG_INFINITY, 4)));
+//! ```
+//!
+//! The crate is also built as a dynamic library exporting the C function
+//! `int parse_float(const char* text, double* value, const char** text_end)`.
+//!
+//! Original place: <https://github.com/turborium/PureParseFloat>
+
+mod bigint;
+mod correct_rounding;
+pub mod double_double;
+pub mod error;
+pub mod fixed_decimal;
+mod ffi;
+mod format;
+mod hex_float;
+mod json;
+pub mod options;
+#[cfg(test)]
+mod random;
+mod reader;
+mod rounding;
+mod status;
+mod unicode;
+
+pub use double_double::DoubleDouble;
+pub use error::ParseFloatError;
+pub use fixed_decimal::FixedDecimal;
+pub use format::{format_f64, format_fixed, format_scientific, FORMAT_F64_MAX_LENGTH};
+pub use json::parse_json_number;
+pub use options::{Locale, ParseOptions, SpecialValues};
+pub use rounding::{parse_float_interval, parse_float_rounded, FloatInterval, RoundingMode};
+pub use status::{parse_float_status, FloatStatus};
+
+use fixed_decimal::read_fixed_decimal;
+use hex_float::{read_hex_float, HexFloat};
+use reader::{CodeUnit, Reader, TextReader};
+use unicode::UnicodeReader;
+
+fn read_inf_or_nan<'a, R: Reader<'a>>(mut p: R, options: &ParseOptions) -> Option<(f64, usize)> {
+    fn common_prefix_length<'a>(mut text: impl Reader<'a>, prefix: &[u8]) -> usize {
         let mut i: usize = 0;
         for &c in prefix {
             if text.ended() || text.get().to_ascii_lowercase() != c {
@@ -210,121 +58,126 @@ fn read_inf_or_nan(mut p: Reader) -> Option<(f64, usize)> {
             i += 1;
             text.advance();
         }
-        return i;
+        i
     }
 
+    let start = p.position();
     let is_negative = match p.get() {
-        b'+' => { p.advance(); false }
+        b'+' if options.leading_plus => { p.advance(); false }
         b'-' => { p.advance(); true }
         _ => false
     };
 
-    match p.get() {
-    b'I' | b'i' => {
-        let len = common_prefix_length(p.clone(), "infinity".as_bytes());
-        if len == 3 || len == 8 {
-            let res = if is_negative { f64::NEG_INFINITY } else { f64::INFINITY };
-            return Some((res, p.1 + len));
-        }
+    // "snan" is "nan" after the letter s
+    let is_signaling = options.nan_payloads && match options.special_values {
+        SpecialValues::AnyCase => matches!(p.get(), b's' | b'S'),
+        SpecialValues::LowerCase => p.get() == b's',
+        _ => false,
+    };
+    if is_signaling {
+        p.advance();
     }
-    b'N' | b'n' => {
-        let len = common_prefix_length(p.clone(), "nan".as_bytes());
-        if len == 3 {
-            let res = if is_negative { -f64::NAN } else { f64::NAN };
-            return Some((res, p.1 + len ));
+
+    // kind and length of the special value
+    let special = match options.special_values {
+    SpecialValues::None => None,
+    SpecialValues::AnyCase | SpecialValues::C => {
+        let infinity_len = common_prefix_length(p.clone(), "infinity".as_bytes());
+        if matches!(infinity_len, 3 | 8) {
+            Some((true, infinity_len))
+        } else if infinity_len > 3 && options.special_values == SpecialValues::C {
+            // C reads "inf" from partial "infinity"
+            Some((true, 3))
+        } else {
+            (common_prefix_length(p.clone(), "nan".as_bytes()) == 3).then_some((false, 3))
         }
     }
-    _ => {},
-    }
-
-    None
-}
-
-fn read_fixed_decimal(mut p: Reader) -> Option<(FixedDecimal, usize)> {
-    const CLIP_EXPONENT: isize = 1000000;
-
-    // read sign
-    let is_negative = match p.get() {
-        b'+' => { p.advance(); false }
-        b'-' => { p.advance(); true },
-        _ => false,
+    SpecialValues::LowerCase => [(true, "inf"), (false, "nan")].into_iter()
+        .find(|(_, word)| p.starts_with(word.as_bytes()))
+        .map(|(is_infinity, word)| (is_infinity, word.len())),
+    SpecialValues::Yaml => [".inf", ".Inf", ".INF"].into_iter()
+        .find(|word| p.starts_with(word.as_bytes()))
+        .map(|word| (true, word.len()))
+        // NaN has no sign
+        .or_else(|| [".nan", ".NaN", ".NAN"].into_iter()
+            .find(|word| p.position() == start && p.starts_with(word.as_bytes()))
+            .map(|word| (false, word.len()))),
     };
 
-    let mut decimal = FixedDecimal{
-        count: 0,
-        digits: [0; FIXED_DECIMAL_DIGITS],
-        exponent: -1,
-        is_negative,
+    let (is_infinity, len) = special?;
+    if is_signaling && is_infinity {
+        return None;
+    }
+    let res = match (is_infinity, is_negative) {
+    (true, false) => f64::INFINITY,
+    (true, true) => f64::NEG_INFINITY,
+    (false, false) => f64::NAN,
+    (false, true) => -f64::NAN,
     };
+    if is_infinity || !options.nan_payloads {
+        return Some((res, p.position() + len));
+    }
 
-    // read mantissa
-    let mut has_digit = false; // has read any digit (0..9)
-    let mut has_point = false; // has read decimal point
-    'read_mantissa_loop: while !p.ended() {
-        match p.get() {
-        b'0'..=b'9' => {
-            if decimal.count != 0 || p.get() != b'0' {
-                // save digit
-                if decimal.count < FIXED_DECIMAL_DIGITS as isize {
-                    decimal.digits[decimal.count as usize] = p.get() - b'0';
-                    decimal.count += 1;
-                }
-                // inc exponenta
-                if !has_point && decimal.exponent < CLIP_EXPONENT {
-                    decimal.exponent += 1;
-                }
-            } else {
-                // skip zero (dec exponenta)
-                if has_point && (decimal.exponent > -CLIP_EXPONENT) {
-                    decimal.exponent -= 1;
-                }
-            }
-            has_digit = true;
-        },
-        b'.' => {
-            if has_point {
-                return Some((decimal, p.1));
-            }
-            has_point = true;
-        },
-        _ => {
-            break 'read_mantissa_loop;
-        },
-        }
+    // quiet NaN has the highest mantissa bit set, signaling NaN needs non zero payload
+    for _ in 0..len {
         p.advance();
     }
+    let (payload, payload_len) = read_nan_payload(p.clone()).unwrap_or((None, 0));
+    let mantissa = payload.unwrap_or(0) & NAN_PAYLOAD_MASK;
+    let bits = match is_signaling {
+    false => f64::NAN.to_bits() | mantissa,
+    true => f64::INFINITY.to_bits() | mantissa.max(1),
+    };
+    Some((f64::from_bits(bits | (is_negative as u64) << 63), p.position() + payload_len))
+}
 
-    if !has_digit {
+// Bits of NaN payload, without the quiet bit
+const NAN_PAYLOAD_MASK: u64 = (1 << 51) - 1;
+
+// Read NaN payload "(n-char-sequence)" after "nan", returns its value and length,
+// or None without closing parenthesis.
+// Like C strtod the sequence is read as strtoull with zero base: "0x" starts hexadecimal,
+// "0" starts octal, too big value is saturated. Sequence which is not such integer
+// has no value.
+fn read_nan_payload<'a>(mut p: impl Reader<'a>) -> Option<(Option<u64>, usize)> {
+    let start = p.position();
+    if p.get_ascii() != b'(' {
         return None;
     }
+    p.advance();
 
-    // read exponenta
-    if matches!(p.get(), b'e' | b'E') {
-        let p_start_exponent = p.1;
+    let mut radix: u64 = 10;
+    let mut has_digits = false;
+    if p.get_ascii() == b'0' {
         p.advance();
-
-        let mut exponent: isize = 0;
-        let exponent_sign: isize = match p.get() {
-            b'+' => { p.advance(); 1 },
-            b'-' => { p.advance(); -1 },
-            _ => 1,
-        };
-
-        // read
-        if matches!(p.get(), b'0'..=b'9') {
-            while matches!(p.get(), b'0'..=b'9') {
-                exponent = (exponent * 10 + (p.get() - b'0') as isize).min(CLIP_EXPONENT);
-                p.advance();
-            }
+        if matches!(p.get_ascii(), b'x' | b'X') {
+            p.advance();
+            radix = 16;
         } else {
-            return Some((decimal, p_start_exponent));
+            radix = 8;
+            has_digits = true;
         }
-
-        // fix
-        decimal.exponent = decimal.exponent + exponent_sign * exponent;
     }
 
-    Some((decimal, p.1))
+    let mut value: u64 = 0;
+    let mut is_integer = true;
+    while !p.ended() && (p.get_ascii().is_ascii_alphanumeric() || p.get_ascii() == b'_') {
+        match (p.get_ascii() as char).to_digit(radix as u32) {
+        Some(digit) => {
+            value = value.checked_mul(radix)
+                .and_then(|value| value.checked_add(digit as u64))
+                .unwrap_or(u64::MAX);
+            has_digits = true;
+        }
+        None => is_integer = false,
+        }
+        p.advance();
+    }
+    if p.get_ascii() != b')' {
+        return None;
+    }
+    p.advance();
+    Some(((is_integer && has_digits).then_some(value), p.position() - start))
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -369,59 +222,658 @@ fn read_fixed_decimal(mut p: Reader) -> Option<(FixedDecimal, usize)> {
 // Text,       TextEnd,   Value = 500,         Result = True
 //
 // -------------------------------------------------------------------------------------------------
-fn parse_float_impl(text: Reader) -> Option<(f64, usize)> {
-    if let res@Some(_) = read_inf_or_nan(text.clone()) {
-        return res
-    } else if let Some((decimal, count)) = read_fixed_decimal(text) {
-        Some(((&decimal).into(), count))
-    } else {
-        None
+fn parse_float_impl<'a>(text: impl Reader<'a>) -> Option<(f64, usize)> {
+    read_number(text, &ParseOptions::new(), false)
+        .ok()
+        .map(|(number, count)| (number.to_f64(), count))
+}
+
+// Float point pattern read from text, before conversion to Double
+#[allow(clippy::large_enum_variant)] // short lived, only moved out of the reader
+pub(crate) enum Number {
+    Special(f64),
+    Decimal(FixedDecimal),
+    Hex(HexFloat),
+}
+
+impl Number {
+    pub(crate) fn to_f64(&self) -> f64 {
+        match self {
+        Number::Special(value) => *value,
+        Number::Decimal(decimal) => decimal.into(),
+        Number::Hex(hex) => hex.to_f64(),
+        }
+    }
+
+    // Signed Double-Double with Hi same as to_f64, Lo is remainder of decimal, zero for
+    // infinite and zero Hi
+    pub(crate) fn to_double_double(&self) -> DoubleDouble {
+        match self {
+        Number::Special(value) => DoubleDouble::from(*value),
+        Number::Decimal(decimal) => {
+            let number = decimal.to_double_double();
+            let hi = decimal.to_f64();
+            let lo = if hi.is_finite() && hi != 0.0 && number.hi.is_finite() { (number - hi.abs()).hi } else { 0.0 };
+            DoubleDouble{
+                hi,
+                lo: if decimal.is_negative() { -lo } else { lo },
+            }
+        }
+        Number::Hex(hex) => DoubleDouble::from(hex.to_f64()),
+        }
+    }
+
+    pub(crate) fn to_f32(&self) -> f32 {
+        match self {
+        Number::Special(value) => *value as f32,
+        Number::Decimal(decimal) => decimal.into(),
+        Number::Hex(hex) => hex.to_f32(),
+        }
+    }
+
+    // Check converted value, finite number going to infinity or non zero number going to zero
+    // is an error
+    pub(crate) fn check_range(&self, res: f64) -> Result<f64, ParseFloatError> {
+        let is_zero = match self {
+        Number::Special(_) => return Ok(res),
+        Number::Decimal(decimal) => decimal.digits().is_empty(),
+        Number::Hex(hex) => hex.is_zero(),
+        };
+        if res.is_infinite() {
+            return Err(ParseFloatError::Overflow);
+        }
+        if res == 0.0 && !is_zero {
+            return Err(ParseFloatError::Underflow);
+        }
+        Ok(res)
+    }
+}
+
+// Skip whitespace chars like C isspace
+fn skip_whitespace<'a>(p: &mut impl Reader<'a>) {
+    while !p.ended() && matches!(p.get(), b' ' | b'\t'..=b'\r') {
+        p.advance();
     }
 }
 
-#[no_mangle]
-unsafe extern fn parse_float(text: *const c_char, value: *mut c_double, text_end: *mut *const c_char) -> c_int {
-    match parse_float_impl(Reader::from_raw_ptr(text as *const u8)) {
-    Some((res, end)) => {
-        *value = res;
-        *text_end = text.add(end);
-        1
+pub(crate) fn read_number<'a>(mut text: impl Reader<'a>, options: &ParseOptions, strict: bool) -> Result<(Number, usize), ParseFloatError> {
+    if options.leading_whitespace {
+        skip_whitespace(&mut text);
     }
-    None => 0
+    if text.ended() {
+        return Err(ParseFloatError::Empty);
     }
+    let (number, count) = read_pattern(text.clone(), options, strict)?;
+    if !options.trailing_whitespace {
+        return Ok((number, count));
+    }
+
+    // move to the end of the number
+    while text.position() < count {
+        text.advance();
+    }
+    skip_whitespace(&mut text);
+    Ok((number, text.position()))
+}
+
+// Read number from code units, mapped to ASCII when the options ask for Unicode
+fn read_units<T: CodeUnit>(text: &[T], options: &ParseOptions, strict: bool) -> Result<(Number, usize), ParseFloatError> {
+    if options.unicode {
+        return read_number(UnicodeReader::new(text), options, strict);
+    }
+    read_number(TextReader::new(text), options, strict)
+}
+
+fn read_pattern<'a>(text: impl Reader<'a>, options: &ParseOptions, strict: bool) -> Result<(Number, usize), ParseFloatError> {
+    if let Some((res, count)) = read_inf_or_nan(text.clone(), options) {
+        return Ok((Number::Special(res), count));
+    }
+    if options.hex_floats {
+        if let Some(res) = read_hex_float(text.clone(), options, strict) {
+            return res.map(|(hex, count)| (Number::Hex(hex), count));
+        }
+    }
+    read_fixed_decimal(text, options, strict).map(|(decimal, count)| (Number::Decimal(decimal), count))
+}
+
+/// Parse float from the start of bytes, never reads past the end of the slice.
+///
+/// On success returns the value and count of bytes matching the float point pattern.
+/// The pattern is a regular float number, with an optional exponent (E/e) and optional (+/-) sign.
+/// The pattern allows the values Inf/Infinity and NaN, with any register and optional sign.
+/// Leading spaces are not allowed in the pattern. A dot is always used as separator.
+pub fn parse_float(text: &[u8]) -> Option<(f64, usize)> {
+    parse_float_impl(TextReader::new(text))
+}
+
+/// Same as [`parse_float`], count is a byte offset into the str
+pub fn parse_float_str(text: &str) -> Option<(f64, usize)> {
+    parse_float(text.as_bytes())
+}
+
+/// Same as [`parse_float`], with grammar changed by options.
+///
+/// ```
+/// use pure_parse_float::{parse_float_with, ParseOptions};
+///
+/// let options = ParseOptions::new().exponent_chars(b"^").leading_plus(false);
+/// assert_eq!(parse_float_with(b"2.5^3e", &options), Some((2500.0, 5)));
+/// assert_eq!(parse_float_with(b"+1", &options), None);
+/// ```
+pub fn parse_float_with(text: &[u8], options: &ParseOptions) -> Option<(f64, usize)> {
+    read_units(text, options, false)
+        .ok()
+        .map(|(number, count)| (number.to_f64(), count))
+}
+
+/// Same as [`parse_float_with`] for UTF-16 text, count is in code units.
+///
+/// Chars out of ASCII are never a part of the pattern, except digit and grouping separators.
+///
+/// ```
+/// use pure_parse_float::{parse_float_utf16, Locale, ParseOptions};
+///
+/// let text: Vec<u16> = "-1.5e3 м".encode_utf16().collect();
+/// assert_eq!(parse_float_utf16(&text, &ParseOptions::new()), Some((-1500.0, 6)));
+/// let text: Vec<u16> = "1\u{202F}234,5".encode_utf16().collect();
+/// assert_eq!(parse_float_utf16(&text, &ParseOptions::new().locale(Locale::FR)), Some((1234.5, 7)));
+/// ```
+pub fn parse_float_utf16(text: &[u16], options: &ParseOptions) -> Option<(f64, usize)> {
+    read_units(text, options, false)
+        .ok()
+        .map(|(number, count)| (number.to_f64(), count))
+}
+
+/// Same as [`parse_float_utf16`] for UTF-32 text.
+pub fn parse_float_utf32(text: &[u32], options: &ParseOptions) -> Option<(f64, usize)> {
+    read_units(text, options, false)
+        .ok()
+        .map(|(number, count)| (number.to_f64(), count))
+}
+
+/// Same as [`parse_float`], but converts to Single directly, without double rounding
+/// through Double.
+///
+/// ```
+/// use pure_parse_float::parse_f32;
+///
+/// // exactly halfway between two Singles after rounding to Double
+/// assert_eq!(parse_f32(b"1.00000017881393432617187499"), Some((1.0000001, 28)));
+/// ```
+pub fn parse_f32(text: &[u8]) -> Option<(f32, usize)> {
+    read_number(TextReader::new(text), &ParseOptions::new(), false)
+        .ok()
+        .map(|(number, count)| (number.to_f32(), count))
+}
+
+/// Same as [`parse_float`], but keeps the remainder of Double-Double conversion:
+/// `hi` is the same value as of [`parse_float`] and `hi + lo` is the decimal value
+/// with about 31 significant digits, see [`crate`] docs for accuracy.
+///
+/// Digits after the first 34 significant digits are ignored. `lo` is zero for Inf, NaN, zero
+/// and overflow.
+///
+/// ```
+/// use pure_parse_float::parse_double_double;
+///
+/// let (pi, count) = parse_double_double(b"3.14159265358979323846264338327950288").unwrap();
+/// assert_eq!((pi.hi, count), (std::f64::consts::PI, 37));
+/// assert!((pi.lo - 1.2246467991473532e-16).abs() < 1e-31);
+/// let (third, _) = parse_double_double(b"0.3333333333333333333333333333333").unwrap();
+/// assert_eq!(format!("{third}"), "3.333333333333333333333333333333e-1");
+/// ```
+pub fn parse_double_double(text: &[u8]) -> Option<(DoubleDouble, usize)> {
+    read_number(TextReader::new(text), &ParseOptions::new(), false)
+        .ok()
+        .map(|(number, count)| (number.to_double_double(), count))
+}
+
+/// Same as [`parse_float`], but reports why the text does not match the pattern,
+/// and reports overflow to infinity and underflow to zero as errors.
+///
+/// ```
+/// use pure_parse_float::{try_parse_float, ParseFloatError};
+///
+/// assert_eq!(try_parse_float(b"500e"), Ok((500.0, 3)));
+/// assert_eq!(try_parse_float(b"-x"), Err(ParseFloatError::InvalidCharacter(1)));
+/// assert_eq!(try_parse_float(b"1e400"), Err(ParseFloatError::Overflow));
+/// ```
+pub fn try_parse_float(text: &[u8]) -> Result<(f64, usize), ParseFloatError> {
+    let (number, count) = read_number(TextReader::new(text), &ParseOptions::new(), false)?;
+    Ok((number.check_range(number.to_f64())?, count))
+}
+
+/// Parse float from the whole text, succeeds only if all bytes match the float point pattern.
+///
+/// Errors are the same as [`try_parse_float`], text continuing after the float
+/// is an [`InvalidCharacter`](ParseFloatError::InvalidCharacter) error.
+///
+/// ```
+/// use pure_parse_float::{parse_float_exact, ParseFloatError};
+///
+/// assert_eq!(parse_float_exact(b"-12.5e3"), Ok(-12.5e3));
+/// assert_eq!(parse_float_exact(b"1.2.3"), Err(ParseFloatError::InvalidCharacter(3)));
+/// assert_eq!(parse_float_exact(b"500e"), Err(ParseFloatError::DanglingExponent(3)));
+/// ```
+pub fn parse_float_exact(text: &[u8]) -> Result<f64, ParseFloatError> {
+    parse_float_exact_with(text, &ParseOptions::new())
+}
+
+/// Same as [`parse_float_exact`], with grammar changed by options.
+///
+/// ```
+/// use pure_parse_float::{parse_float_exact_with, ParseFloatError, ParseOptions};
+///
+/// let options = ParseOptions::new().trailing_whitespace(true).require_digits_after_point(true);
+/// assert_eq!(parse_float_exact_with(b"1.5 \n", &options), Ok(1.5));
+/// assert_eq!(parse_float_exact_with(b"1.e5", &options), Err(ParseFloatError::InvalidCharacter(1)));
+/// ```
+pub fn parse_float_exact_with(text: &[u8], options: &ParseOptions) -> Result<f64, ParseFloatError> {
+    let (number, count) = read_units(text, options, true)?;
+    if count != text.len() {
+        return Err(ParseFloatError::InvalidCharacter(count));
+    }
+    number.check_range(number.to_f64())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_float_impl, Reader};
+    use crate::{parse_double_double, parse_f32, parse_float, parse_float_exact, parse_float_exact_with, parse_float_impl, parse_float_str, parse_float_utf16, parse_float_utf32, parse_float_with, try_parse_float, FixedDecimal, Locale, ParseFloatError, ParseOptions};
+    use crate::random::{random_generator, EDGE_DECIMALS};
+    use crate::reader::{PointerReader, SliceReader};
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn pi() {
-        let (result, _) = parse_float_impl(Reader::from_str("3.14159265")).unwrap();
+        let (result, _) = parse_float_str("3.14159265").unwrap();
         assert_eq!(result, 3.14159265);
     }
 
     #[test]
     fn exponent() {
-        let (result, _) = parse_float_impl(Reader::from_str("-1234e10")).unwrap();
+        let (result, _) = parse_float_str("-1234e10").unwrap();
         assert_eq!(result, -1234e10);
     }
 
     #[test]
     fn nan() {
-        let (result, _) = parse_float_impl(Reader::from_str("nan")).unwrap();
+        let (result, _) = parse_float_str("nan").unwrap();
         assert!(f64::is_nan(result));
     }
 
     #[test]
     fn minus_nan() {
-        let (result, _) = parse_float_impl(Reader::from_str("-nan")).unwrap();
+        let (result, _) = parse_float_str("-nan").unwrap();
         assert!(f64::is_nan(result));
     }
 
     #[test]
     fn minus_nan_uppercase() {
-        let (result, _) = parse_float_impl(Reader::from_str("-NaN")).unwrap();
+        let (result, _) = parse_float_str("-NaN").unwrap();
         assert!(f64::is_nan(result));
     }
+
+    #[test]
+    fn slice_bounds() {
+        assert_eq!(parse_float(&b"12345"[..3]), Some((123.0, 3)));
+        assert_eq!(parse_float(&b"1.5e+7"[..4]), Some((1.5, 3)));
+        assert_eq!(parse_float(&b"infinity"[..3]), Some((f64::INFINITY, 3)));
+        assert_eq!(parse_float(&b"-"[..]), None);
+        assert_eq!(parse_float(b""), None);
+    }
+
+    #[test]
+    fn end_offset() {
+        assert_eq!(parse_float_str("+123.45e-22 abc"), Some((123.45e-22, 11)));
+        assert_eq!(parse_float_str("500e"), Some((500.0, 3)));
+        assert_eq!(parse_float_str("1.2.3"), Some((1.2, 3)));
+        assert_eq!(parse_float_str(".."), Some((0.0, 1)));
+        assert_eq!(parse_float_str("+.."), Some((0.0, 2)));
+        assert_eq!(parse_float_str("-..5"), Some((0.0, 2)));
+        assert_eq!(parse_float_str("aboba"), None);
+    }
+
+    #[test]
+    fn fixed_decimal() {
+        let (decimal, count) = FixedDecimal::parse(b"-00120.50e3x").unwrap();
+        assert_eq!(count, 11);
+        assert_eq!(decimal.digits(), &[1, 2, 0, 5, 0]);
+        assert_eq!(decimal.exponent(), 5);
+        assert!(decimal.is_negative());
+        assert_eq!(decimal.to_f64(), -120.5e3);
+        assert!(FixedDecimal::parse(b"inf").is_none());
+    }
+
+    #[test]
+    fn scanners_match() {
+        let mut next = random_generator(0x2545F4914F6CDD1D);
+        const ALPHABET: &[u8] = b"0123456789.eE+-infINFaty x";
+        for _ in 0..200_000 {
+            let len = (next() % 24) as usize;
+            let text: Vec<u8> = (0..len).map(|_| ALPHABET[(next() % ALPHABET.len() as u64) as usize]).collect();
+            for end in [len, len / 2] {
+                let slice = parse_float_impl(SliceReader::new(&text[..end]));
+                let pointer = parse_float_impl(PointerReader::new(&text[..end]));
+                assert_eq!(slice.map(|(v, n)| (v.to_bits(), n)), pointer.map(|(v, n)| (v.to_bits(), n)));
+
+                let utf16: Vec<u16> = text[..end].iter().map(|&c| c as u16).collect();
+                let utf32: Vec<u32> = text[..end].iter().map(|&c| c as u32).collect();
+                for wide in [
+                    parse_float_impl(SliceReader::new(&utf16[..])), parse_float_impl(PointerReader::new(&utf16[..])),
+                    parse_float_impl(SliceReader::new(&utf32[..])), parse_float_impl(PointerReader::new(&utf32[..])),
+                ] {
+                    assert_eq!(slice.map(|(v, n)| (v.to_bits(), n)), wide.map(|(v, n)| (v.to_bits(), n)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overflow_and_underflow() {
+        assert_eq!(parse_float_str("9e400"), Some((f64::INFINITY, 5)));
+        assert_eq!(parse_float_str("-1e1000000000"), Some((f64::NEG_INFINITY, 13)));
+        assert_eq!(parse_float_str("9e-400"), Some((0.0, 6)));
+        assert_eq!(parse_float_str("4.9406564584124654e-324"), Some((5e-324, 23)));
+    }
+
+    #[test]
+    fn exact() {
+        assert_eq!(parse_float_exact(b"1984"), Ok(1984.0));
+        assert_eq!(parse_float_exact(b"-Infinity"), Ok(f64::NEG_INFINITY));
+        assert_eq!(parse_float_exact(b"500e"), Err(ParseFloatError::DanglingExponent(3)));
+        assert_eq!(parse_float_exact(b"500E+"), Err(ParseFloatError::DanglingExponent(3)));
+        assert_eq!(parse_float_exact(b"1.2.3"), Err(ParseFloatError::InvalidCharacter(3)));
+        assert_eq!(parse_float_exact(b"12 "), Err(ParseFloatError::InvalidCharacter(2)));
+        assert_eq!(parse_float_exact(b"infin"), Err(ParseFloatError::InvalidCharacter(0)));
+        assert_eq!(parse_float_exact(b" 12"), Err(ParseFloatError::InvalidCharacter(0)));
+        assert_eq!(parse_float_exact(b""), Err(ParseFloatError::Empty));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(try_parse_float(b"500e"), Ok((500.0, 3)));
+        assert_eq!(try_parse_float(b"-"), Err(ParseFloatError::NoDigits));
+        assert_eq!(try_parse_float(b"+."), Err(ParseFloatError::NoDigits));
+        assert_eq!(try_parse_float(b"-.x"), Err(ParseFloatError::InvalidCharacter(2)));
+        assert_eq!(try_parse_float(b"-1e400"), Err(ParseFloatError::Overflow));
+        assert_eq!(try_parse_float(b"1e-400"), Err(ParseFloatError::Underflow));
+        assert_eq!(try_parse_float(b"0e-400"), Ok((0.0, 6)));
+        assert_eq!(try_parse_float(b"-inf"), Ok((f64::NEG_INFINITY, 4)));
+        assert_eq!(ParseFloatError::DanglingExponent(3).to_string(), "exponent without digits at byte 3");
+    }
+
+    #[test]
+    fn options() {
+        let default = ParseOptions::default();
+        assert_eq!(parse_float_with(b"+1.5e3x", &default), Some((1500.0, 6)));
+        assert_eq!(parse_float_with(b" 1", &default), None);
+
+        let comma = ParseOptions::new().decimal_separator(b',');
+        assert_eq!(parse_float_with(b"-1,25", &comma), Some((-1.25, 5)));
+        assert_eq!(parse_float_with(b"1.25", &comma), Some((1.0, 1)));
+        assert_eq!(parse_float_with(b"1,2,3", &comma), Some((1.2, 3)));
+        assert_eq!(parse_float_exact_with(b"1,2,3", &comma), Err(ParseFloatError::InvalidCharacter(3)));
+
+        let no_plus = ParseOptions::new().leading_plus(false);
+        assert_eq!(parse_float_with(b"-1e+2", &no_plus), Some((-100.0, 5)));
+        assert_eq!(try_parse_float(b"+1"), Ok((1.0, 2)));
+        assert_eq!(parse_float_exact_with(b"+1", &no_plus), Err(ParseFloatError::InvalidCharacter(0)));
+        assert_eq!(parse_float_exact_with(b"+inf", &no_plus), Err(ParseFloatError::InvalidCharacter(0)));
+
+        let no_specials = ParseOptions::new().special_values(false);
+        assert_eq!(parse_float_with(b"-Infinity", &no_specials), None);
+        assert_eq!(parse_float_exact_with(b"nan", &no_specials), Err(ParseFloatError::InvalidCharacter(0)));
+
+        let spaces = ParseOptions::new().leading_whitespace(true).trailing_whitespace(true);
+        assert_eq!(parse_float_with(b" \t\n\x0B\x0C\r7 \r\nx", &spaces), Some((7.0, 10)));
+        assert_eq!(parse_float_with(b"  inf  ", &spaces), Some((f64::INFINITY, 7)));
+        assert_eq!(parse_float_with(b"  500e  ", &spaces), Some((500.0, 5)));
+        assert_eq!(parse_float_exact_with(b"   ", &spaces), Err(ParseFloatError::Empty));
+        assert_eq!(parse_float_exact_with(b" 1 2", &spaces), Err(ParseFloatError::InvalidCharacter(3)));
+
+        let digits = ParseOptions::new().require_digits_before_point(true).require_digits_after_point(true);
+        assert_eq!(parse_float_with(b"0.5", &digits), Some((0.5, 3)));
+        assert_eq!(parse_float_with(b".5", &digits), None);
+        assert_eq!(parse_float_with(b"-5.", &digits), Some((-5.0, 2)));
+        assert_eq!(parse_float_with(b"5.e3", &digits), Some((5.0, 1)));
+        assert_eq!(parse_float_exact_with(b"-.5", &digits), Err(ParseFloatError::InvalidCharacter(1)));
+        assert_eq!(parse_float_exact_with(b"5.", &digits), Err(ParseFloatError::InvalidCharacter(1)));
+
+        let exponent = ParseOptions::new().exponent_chars(b"dD");
+        assert_eq!(parse_float_with(b"1.5D-2", &exponent), Some((0.015, 6)));
+        assert_eq!(parse_float_with(b"1.5e-2", &exponent), Some((1.5, 3)));
+        assert_eq!(parse_float_with(b"1e2", &ParseOptions::new().exponent_chars(b"")), Some((1.0, 1)));
+
+        let hex = ParseOptions::new().hex_floats(true).decimal_separator(b',').leading_plus(false);
+        assert_eq!(parse_float_with(b"0x1,8p1", &hex), Some((3.0, 7)));
+        assert_eq!(parse_float_with(b"+0x1p1", &hex), None);
+    }
+
+    #[test]
+    fn digit_separator() {
+        let underscore = ParseOptions::new().digit_separator(Some('_'));
+        assert_eq!(parse_float_with(b"1_000_000.5", &underscore), Some((1000000.5, 11)));
+        assert_eq!(parse_float_with(b"-0.000_001e1_0", &underscore), Some((-1e4, 14)));
+        assert_eq!(parse_float_with(b"1_000", &ParseOptions::new()), Some((1.0, 1)));
+        assert_eq!(parse_float_with(b"1__000", &underscore), Some((1.0, 1)));
+        assert_eq!(parse_float_with(b"1_000_", &underscore), Some((1000.0, 5)));
+        assert_eq!(parse_float_with(b"1_.5", &underscore), Some((1.0, 1)));
+        assert_eq!(parse_float_with(b"1._5", &underscore), Some((1.0, 2)));
+        assert_eq!(parse_float_with(b"1e_5", &underscore), Some((1.0, 1)));
+        assert_eq!(parse_float_with(b"1e5_", &underscore), Some((1e5, 3)));
+        assert_eq!(parse_float_with(b"_1", &underscore), None);
+        assert_eq!(parse_float_exact_with(b"1__000", &underscore), Err(ParseFloatError::InvalidCharacter(1)));
+        assert_eq!(parse_float_exact_with(b"1_000_", &underscore), Err(ParseFloatError::InvalidCharacter(5)));
+        assert_eq!(parse_float_exact_with(b"1._5", &underscore), Err(ParseFloatError::InvalidCharacter(2)));
+        assert_eq!(parse_float_exact_with(b"1e_5", &underscore), Err(ParseFloatError::DanglingExponent(1)));
+
+        let apostrophe = ParseOptions::new().digit_separator(Some('\''));
+        assert_eq!(parse_float_exact_with(b"1'000'000", &apostrophe), Ok(1e6));
+
+        let thin_space = ParseOptions::new().digit_separator(Some('\u{2009}'));
+        assert_eq!(parse_float_with("12\u{2009}345,5".as_bytes(), &thin_space), Some((12345.0, 8)));
+        assert_eq!(parse_float_with("12\u{2009}\u{2009}3".as_bytes(), &thin_space), Some((12.0, 2)));
+        assert_eq!(parse_float_with("12\u{2008}3".as_bytes(), &thin_space), Some((12.0, 2)));
+    }
+
+    #[test]
+    fn locales() {
+        let en = ParseOptions::new().locale(Locale::EN);
+        assert_eq!(parse_float_exact_with(b"1,234,567.89", &en), Ok(1234567.89));
+        assert_eq!(parse_float_exact_with(b"-12,345", &en), Ok(-12345.0));
+        assert_eq!(parse_float_exact_with(b"1234567.89", &en), Ok(1234567.89));
+        assert_eq!(parse_float_with(b"1,234 apples", &en), Some((1234.0, 5)));
+        assert_eq!(parse_float_with(b"1, 2", &en), Some((1.0, 1)));
+        assert_eq!(parse_float_with(b"1,,234", &en), Some((1.0, 1)));
+        assert_eq!(parse_float_with(b"0.123,456", &en), Some((0.123, 5)));
+        assert_eq!(parse_float_exact_with(b"1234,567", &en), Err(ParseFloatError::InvalidCharacter(4)));
+        assert_eq!(parse_float_exact_with(b"1,2345", &en), Err(ParseFloatError::InvalidCharacter(5)));
+        assert_eq!(parse_float_exact_with(b"1,23.5", &en), Err(ParseFloatError::InvalidCharacter(4)));
+        assert_eq!(parse_float_exact_with(b"1,234,56", &en), Err(ParseFloatError::InvalidCharacter(8)));
+        assert_eq!(parse_float_exact_with(b"1,23,456", &en), Err(ParseFloatError::InvalidCharacter(4)));
+        // reading stops before the separator of a bad group
+        for (text, expected) in [
+            ("1,23e5", (1.0, 1)), ("1,23", (1.0, 1)), ("1,2345", (1.0, 1)), ("1234,567", (1234.0, 4)),
+            ("1234,567e1", (1234.0, 4)), ("1,234,56", (1234.0, 5)), ("1,234,5678", (1234.0, 5)),
+            ("1,23,456", (1.0, 1)), ("-1,23.5", (-1.0, 2)), ("1,234,567.5", (1234567.5, 11)),
+        ] {
+            assert_eq!(parse_float_with(text.as_bytes(), &en), Some(expected), "{text}");
+        }
+
+        let de = ParseOptions::new().locale(Locale::DE);
+        assert_eq!(parse_float_exact_with(b"1.234.567,89", &de), Ok(1234567.89));
+        assert_eq!(parse_float_exact_with(b"2,75", &de), Ok(2.75));
+        assert_eq!(parse_float_exact_with(b"1.000e3", &de), Ok(1e6));
+        assert_eq!(parse_float_exact_with(b"2.75", &de), Err(ParseFloatError::InvalidCharacter(4)));
+
+        let fr = ParseOptions::new().locale(Locale::FR);
+        assert_eq!(parse_float_exact_with("1\u{202F}234\u{202F}567,89".as_bytes(), &fr), Ok(1234567.89));
+        assert_eq!(parse_float_with("1 234".as_bytes(), &fr), Some((1.0, 1)));
+
+        let ru = ParseOptions::new().locale(Locale::RU);
+        assert_eq!(parse_float_exact_with("-1\u{A0}234,5".as_bytes(), &ru), Ok(-1234.5));
+        assert_eq!(parse_float_with("12\u{A0}34,5".as_bytes(), &ru), Some((12.0, 2)));
+
+        let custom = ParseOptions::new().locale(Locale::new(b'.', '\''));
+        assert_eq!(parse_float_exact_with(b"1'234.5", &custom), Ok(1234.5));
+    }
+
+    #[test]
+    fn nan_payloads() {
+        let options = ParseOptions::new().nan_payloads(true);
+        let parse = |text: &str| parse_float_with(text.as_bytes(), &options).map(|(v, n)| (v.to_bits(), n));
+        for (text, bits, count) in [
+            ("nan(123)", 0x7FF8_0000_0000_007B, 8), ("NaN(0x7b)", 0x7FF8_0000_0000_007B, 9),
+            ("nan(0173)", 0x7FF8_0000_0000_007B, 9), ("-nan(1)", 0xFFF8_0000_0000_0001, 7),
+            ("nan(0x8000000000001)", 0x7FF8_0000_0000_0001, 20), ("nan(99999999999999999999)", 0x7FFF_FFFF_FFFF_FFFF, 25),
+            ("nan()", 0x7FF8_0000_0000_0000, 5), ("nan(abc_1)", 0x7FF8_0000_0000_0000, 10),
+            ("nan(0x)", 0x7FF8_0000_0000_0000, 7), ("nan(09)", 0x7FF8_0000_0000_0000, 7),
+            ("nan(1", 0x7FF8_0000_0000_0000, 3), ("nan(1 )", 0x7FF8_0000_0000_0000, 3),
+            ("snan", 0x7FF0_0000_0000_0001, 4), ("-SNaN(5)", 0xFFF0_0000_0000_0005, 8),
+            ("snan(0)", 0x7FF0_0000_0000_0001, 7), ("snan(0x8000000000000)", 0x7FF0_0000_0000_0001, 21),
+        ] {
+            assert_eq!(parse(text), Some((bits, count)), "{text}");
+        }
+        assert_eq!(parse("sinf"), None);
+        assert_eq!(parse("s"), None);
+
+        let lower = options.clone().special_value_style(crate::SpecialValues::LowerCase);
+        assert_eq!(parse_float_with(b"snan(2)", &lower).map(|(v, n)| (v.to_bits(), n)), Some((0x7FF0_0000_0000_0002, 7)));
+        assert_eq!(parse_float_with(b"SNAN", &lower), None);
+
+        // payload is ignored by default
+        assert_eq!(parse_float_str("nan(123)").map(|(v, n)| (v.to_bits(), n)), Some((f64::NAN.to_bits(), 3)));
+        assert_eq!(parse_float_str("snan"), None);
+    }
+
+    #[test]
+    #[cfg(all(unix, target_env = "gnu"))]
+    fn nan_payloads_same_as_strtod() {
+        use std::ffi::{c_char, CString};
+
+        extern "C" {
+            fn strtod(text: *const c_char, text_end: *mut *mut c_char) -> f64;
+        }
+
+        let mut next = random_generator(0x6A09E667F3BCC908);
+        let options = ParseOptions::new().nan_payloads(true);
+        for _ in 0..10_000 {
+            let mut text = String::from(["nan(", "-NaN(", "nan(0x", "nan(0", "nan(0X"][(next() % 5) as usize]);
+            for _ in 0..next() % 24 {
+                text.push(b"0123456789abcdefxAF_z)( "[(next() % 24) as usize] as char);
+            }
+            if !next().is_multiple_of(4) {
+                text.push(')');
+            }
+
+            let c_text = CString::new(text.clone()).unwrap();
+            let mut c_end: *mut c_char = std::ptr::null_mut();
+            let expected = unsafe { strtod(c_text.as_ptr(), &mut c_end) };
+            let expected_count = c_end as usize - c_text.as_ptr() as usize;
+            let res = parse_float_with(text.as_bytes(), &options).map(|(v, n)| (v.to_bits(), n));
+            assert_eq!(res, Some((expected.to_bits(), expected_count)), "{text}");
+        }
+    }
+
+    #[test]
+    fn wide_text() {
+        let utf16 = |text: &str| text.encode_utf16().collect::<Vec<u16>>();
+        let utf32 = |text: &str| text.chars().map(|c| c as u32).collect::<Vec<u32>>();
+        let options = ParseOptions::new();
+        assert_eq!(parse_float_utf16(&utf16("+12.5e-1x"), &options), Some((1.25, 8)));
+        assert_eq!(parse_float_utf32(&utf32("-Infinity"), &options), Some((f64::NEG_INFINITY, 9)));
+        // units with ASCII low byte are not ASCII
+        assert_eq!(parse_float_utf16(&[0x31, 0x0132, 0x33], &options), Some((1.0, 1)));
+        assert_eq!(parse_float_utf32(&[0x31, 0x1_0032, 0x33], &options), Some((1.0, 1)));
+        assert_eq!(parse_float_utf16(&[0x012E, 0x35], &options), None);
+
+        // separators out of ASCII and out of BMP
+        let ru = ParseOptions::new().locale(Locale::RU);
+        assert_eq!(parse_float_utf16(&utf16("1\u{A0}234\u{A0}567,5"), &ru), Some((1234567.5, 11)));
+        assert_eq!(parse_float_utf32(&utf32("1\u{A0}234,5"), &ru), Some((1234.5, 7)));
+        let emoji = ParseOptions::new().digit_separator(Some('\u{1F600}'));
+        assert_eq!(parse_float_utf16(&utf16("1\u{1F600}000"), &emoji), Some((1000.0, 6)));
+        assert_eq!(parse_float_utf32(&utf32("1\u{1F600}000"), &emoji), Some((1000.0, 5)));
+        assert_eq!(parse_float_utf16(&utf16("1\u{1F600}"), &emoji), Some((1.0, 1)));
+    }
+
+    #[test]
+    fn single() {
+        assert_eq!(parse_f32(b"3.4028235e38"), Some((f32::MAX, 12)));
+        assert_eq!(parse_f32(b"3.40282357e38"), Some((f32::INFINITY, 13)));
+        assert_eq!(parse_f32(b"-1e39"), Some((f32::NEG_INFINITY, 5)));
+        assert_eq!(parse_f32(b"1e-46"), Some((0.0, 5)));
+        assert_eq!(parse_f32(b"1e-45"), Some((1e-45, 5)));
+        assert_eq!(parse_f32(b"16777217"), Some((16777216.0, 8)));
+        assert_eq!(parse_f32(b"16777217.000000001"), Some((16777218.0, 18)));
+
+        // halfway between Singles and near it, around the min normal and the min subnormal Single,
+        // around the max Single and overflow
+        let mut texts: Vec<String> = [
+            "16777217", "16777219", "16777217.000000001", "16777216.999999999", "1.000000059604644775390625",
+            "1.00000017881393432617187500", "1.0000000596046447753906251", "1.1754942e-38", "1.17549430e-38",
+            "1.1754943508222875e-38", "1.4e-45", "7.006492321624085e-46", "7.006492321624086e-46", "2.1019476964872256e-45",
+            "3.4028235e38", "3.4028235677973362e38", "3.4028235677973366e38", "-3.40282357e38",
+        ].map(String::from).to_vec();
+        let mut next = random_generator(0x853C49E6748FEA9B);
+        for _ in 0..20_000 {
+            // random Single, or exact halfway between two Singles, with a small offset
+            let bits = (next() % 0x7F80_0000) as u32;
+            let value = f32::from_bits(bits) as f64;
+            let half = (f32::from_bits(bits + 1) as f64 - value) / 2.0;
+            texts.push(match next() % 3 {
+            0 => format!("{:e}", value + half),
+            1 => format!("{:.30e}", value + half * 0.9999999),
+            _ => format!("{:.12e}", value),
+            });
+        }
+        for text in texts {
+            let (value, count) = parse_f32(text.as_bytes()).unwrap();
+            let expected: f32 = text.parse().unwrap();
+            assert_eq!(value.to_bits(), expected.to_bits(), "Single of {text}: {value:e} instead of {expected:e}");
+            assert_eq!(count, text.len(), "count of {text}");
+        }
+    }
+
+    #[test]
+    fn double_double() {
+        assert_eq!(parse_double_double(b"-0.5x").map(|(v, n)| (v.hi, v.lo, n)), Some((-0.5, 0.0, 4)));
+        // exact remainders of Double
+        for (text, hi, lo) in [("-0.1", -0.1, 5.551115123125783e-18), ("6.62607015e-34", 6.62607015e-34, 1.7027509332425372e-50)] {
+            let (value, _) = parse_double_double(text.as_bytes()).unwrap();
+            assert_eq!(value.hi, hi, "{text}");
+            assert!((value.lo - lo).abs() <= 1e-31 * hi.abs(), "{text}");
+        }
+        for text in ["inf", "-nan", "1e400", "-0", "1e-400"] {
+            let (value, count) = parse_double_double(text.as_bytes()).unwrap();
+            assert_eq!((value.hi.to_bits(), value.lo, count), (parse_float(text.as_bytes()).unwrap().0.to_bits(), 0.0, text.len()), "{text}");
+        }
+        assert_eq!(parse_double_double(b"e5"), None);
+
+        // Hi is the same as parse_float, Lo is the remainder within half ULP of normal Hi
+        let mut texts: Vec<String> = EDGE_DECIMALS.map(String::from).to_vec();
+        let mut next = random_generator(0xD1B54A32D192ED03);
+        for _ in 0..10_000 {
+            let digits: String = (0..31).map(|i| (b'0' + if i == 0 { next() % 8 + 1 } else { next() % 10 } as u8) as char).collect();
+            texts.push(format!("{}.{}e{}", &digits[..1], &digits[1..], (next() % 580) as i64 - 290));
+        }
+        for (i, text) in texts.iter().enumerate() {
+            let (value, count) = parse_double_double(text.as_bytes()).unwrap();
+            let expected = parse_float(text.as_bytes()).unwrap().0;
+            assert_eq!(value.hi.to_bits(), expected.to_bits(), "Hi of {text}: {:e} instead of {expected:e}", value.hi);
+            assert_eq!(count, text.len(), "count of {text}");
+            let hi = value.hi.abs();
+            assert!(!hi.is_normal() || value.lo.abs() <= (hi.next_up() - hi) / 2.0, "remainder of {text}: Hi {:e}, Lo {:e}", value.hi, value.lo);
+
+            // 31 random digits are kept within one in the last digit
+            if i >= EDGE_DECIMALS.len() {
+                let res = format!("{value:.30}");
+                let (mantissa, exponent) = res.split_once('e').unwrap();
+                let (digits, expected_exponent) = text.split_once('e').unwrap();
+                assert_eq!(exponent, expected_exponent, "exponent of {text}: {res}");
+                let difference = mantissa.replace('.', "").parse::<i128>().unwrap() - digits.replace('.', "").parse::<i128>().unwrap();
+                assert!(difference.abs() <= 1, "digits of {text}: {res}");
+            }
+        }
+    }
 }
@@ -0,0 +1,482 @@
+//! Grammar options of [`parse_float_with`](crate::parse_float_with) and locale profiles
+
+/// Grammar options, the default options give the same grammar as [`parse_float`](crate::parse_float)
+///
+/// ```
+/// use pure_parse_float::{parse_float_with, ParseOptions};
+///
+/// let options = ParseOptions::new().hex_floats(true);
+/// assert_eq!(parse_float_with(b"0x1.8p+1", &options), Some((3.0, 8)));
+///
+/// let options = ParseOptions::new()
+///     .decimal_separator(b',')
+///     .leading_whitespace(true)
+///     .special_values(false);
+/// assert_eq!(parse_float_with(b"  -12,5e1", &options), Some((-125.0, 9)));
+/// assert_eq!(parse_float_with(b"inf", &options), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub(crate) hex_floats: bool,
+    pub(crate) decimal_separator: u8,
+    pub(crate) leading_plus: bool,
+    pub(crate) special_values: SpecialValues,
+    pub(crate) leading_whitespace: bool,
+    pub(crate) trailing_whitespace: bool,
+    pub(crate) require_digits_before_point: bool,
+    pub(crate) require_digits_after_point: bool,
+    pub(crate) exponent_chars: &'static [u8],
+    pub(crate) digit_separator: Option<char>,
+    pub(crate) grouping_separator: Option<char>,
+    pub(crate) leading_zeros: bool,
+    pub(crate) implicit_exponent: bool,
+    pub(crate) nan_payloads: bool,
+    pub(crate) unicode: bool,
+    // "..", "-..5" read as zero up to the second point like the reference implementation
+    pub(crate) number_before_second_point: bool,
+    // second point ends the number in strict mode too, what follows the number is not checked
+    pub(crate) second_point_ends_number: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseOptions {
+    /// Grammar of TOML 1.0 floats and decimal integers: `+1.0`, `224_617.445_991_228`,
+    /// `-2E-2`, `inf`, `-nan`. Leading zeros, point without digits around it and special
+    /// values not in lower case are rejected.
+    pub const fn toml() -> Self {
+        Self::new()
+            .digit_separator(Some('_'))
+            .leading_zeros(false)
+            .require_digits_before_point(true)
+            .require_digits_after_point(true)
+            .special_value_style(SpecialValues::LowerCase)
+    }
+
+    /// Grammar of floats and decimal integers of YAML 1.2 core schema: `0.`, `.5`,
+    /// `+12e03`, `-.Inf`, `.NAN`.
+    pub const fn yaml() -> Self {
+        Self::new().special_value_style(SpecialValues::Yaml)
+    }
+
+    /// Grammar of JSON numbers of RFC 8259: `-0.5`, `1E+2`, `12e007`. Leading `+`, leading
+    /// zeros, point without digits around it, `NaN` and `Infinity` are rejected. Used by
+    /// [`parse_json_number`](crate::parse_json_number), where a second point ends the number.
+    pub const fn json() -> Self {
+        let mut options = Self::new()
+            .leading_plus(false)
+            .special_values(false)
+            .leading_zeros(false)
+            .require_digits_before_point(true)
+            .require_digits_after_point(true);
+        options.second_point_ends_number = true;
+        options
+    }
+
+    /// Grammar of Fortran numbers: exponent with `E`, `D` or `Q` in any register like `1.0D+03`
+    /// and `2.5q-10`, or without exponent char like `1.0+003`.
+    pub const fn fortran() -> Self {
+        Self::new()
+            .exponent_chars(b"eEdDqQ")
+            .implicit_exponent(true)
+    }
+
+    /// Grammar of C99 `strtod` in "C" locale: leading whitespace, hexadecimal floats,
+    /// `inf`, `infinity` and `nan` with payload in any register. Unlike the default grammar,
+    /// text without digits before a second point like `..` is not a number.
+    pub const fn c() -> Self {
+        let mut options = Self::new()
+            .leading_whitespace(true)
+            .hex_floats(true)
+            .special_value_style(SpecialValues::C)
+            .nan_payloads(true);
+        options.number_before_second_point = false;
+        options
+    }
+
+    /// Grammar of Rust `f64::from_str`: optional `+` or `-`, digits with optional point like
+    /// `1.` and `.5`, exponent with `e` or `E`, `inf`, `infinity` and `nan` in any register
+    /// with sign. Without digits before a second point like `..` text is not a number.
+    /// With [`parse_float_exact_with`](crate::parse_float_exact_with) the same texts are
+    /// accepted, but out of range values are reported as errors instead of infinity or zero.
+    pub const fn rust() -> Self {
+        let mut options = Self::new()
+            .leading_plus(true)
+            .special_value_style(SpecialValues::AnyCase)
+            .exponent_chars(b"eE")
+            .leading_zeros(true)
+            .require_digits_before_point(false)
+            .require_digits_after_point(false);
+        options.number_before_second_point = false;
+        options
+    }
+
+    /// Default grammar
+    pub const fn new() -> Self {
+        ParseOptions{
+            hex_floats: false,
+            decimal_separator: b'.',
+            leading_plus: true,
+            special_values: SpecialValues::AnyCase,
+            leading_whitespace: false,
+            trailing_whitespace: false,
+            require_digits_before_point: false,
+            require_digits_after_point: false,
+            exponent_chars: b"eE",
+            digit_separator: None,
+            grouping_separator: None,
+            leading_zeros: true,
+            implicit_exponent: false,
+            nan_payloads: false,
+            unicode: false,
+            number_before_second_point: true,
+            second_point_ends_number: false,
+        }
+    }
+
+    /// Accept C99 hexadecimal floats with `0x`/`0X` mantissa and optional `p`/`P` binary
+    /// exponent, like `0x1.921fb54442d18p+1`. Hex floats are converted exactly, with correct
+    /// rounding of over-long mantissas.
+    pub const fn hex_floats(mut self, value: bool) -> Self {
+        self.hex_floats = value;
+        self
+    }
+
+    /// Byte separating integer and fractional part of the mantissa, `.` by default.
+    /// Should not be a digit, sign or exponent char.
+    pub const fn decimal_separator(mut self, value: u8) -> Self {
+        self.decimal_separator = value;
+        self
+    }
+
+    /// Accept `+` sign before the mantissa, enabled by default.
+    /// Sign of the exponent is not affected.
+    pub const fn leading_plus(mut self, value: bool) -> Self {
+        self.leading_plus = value;
+        self
+    }
+
+    /// Accept Inf/Infinity and NaN in any register, enabled by default,
+    /// same as [`SpecialValues::AnyCase`] and [`SpecialValues::None`].
+    pub const fn special_values(mut self, value: bool) -> Self {
+        self.special_values = if value { SpecialValues::AnyCase } else { SpecialValues::None };
+        self
+    }
+
+    /// Spelling of infinity and NaN, [`SpecialValues::AnyCase`] by default.
+    pub const fn special_value_style(mut self, value: SpecialValues) -> Self {
+        self.special_values = value;
+        self
+    }
+
+    /// Read text as Unicode chars, disabled by default. Decimal digits of any script
+    /// (general category Nd) are read as ASCII digits, minus sign U+2212 as `-` and
+    /// whitespace like no-break space as space. Count is still in code units of the text,
+    /// invalid code units are not a part of the pattern. Hexadecimal floats after the sign
+    /// and NaN payloads are read as ASCII only.
+    ///
+    /// ```
+    /// use pure_parse_float::{parse_float_with, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().unicode(true);
+    /// let text = "\u{2212}\u{FF11}\u{FF12}.\u{0665} kg";
+    /// assert_eq!(parse_float_with(text.as_bytes(), &options), Some((-12.5, 12)));
+    /// ```
+    pub const fn unicode(mut self, value: bool) -> Self {
+        self.unicode = value;
+        self
+    }
+
+    /// Accept NaN payload like C `strtod` and signaling NaN, disabled by default.
+    /// Works with [`SpecialValues::AnyCase`] and [`SpecialValues::LowerCase`] spelling,
+    /// [`SpecialValues::C`] has payloads without signaling NaN.
+    ///
+    /// `nan(n-char-sequence)` with decimal, `0x` hexadecimal or `0` octal integer sequence
+    /// puts its lower 51 bits to the mantissa of quiet NaN, other sequences of letters, digits
+    /// and `_` give the default NaN. `snan` and `snan(...)` give signaling NaN, with payload 1
+    /// when the payload is zero. Without closing parenthesis only `nan` is read.
+    ///
+    /// ```
+    /// use pure_parse_float::{parse_float_with, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().nan_payloads(true);
+    /// let (value, count) = parse_float_with(b"nan(0x2a)", &options).unwrap();
+    /// assert_eq!((value.to_bits(), count), (0x7FF8_0000_0000_002A, 9));
+    /// let (value, _) = parse_float_with(b"-snan", &options).unwrap();
+    /// assert_eq!(value.to_bits(), 0xFFF0_0000_0000_0001);
+    /// ```
+    pub const fn nan_payloads(mut self, value: bool) -> Self {
+        self.nan_payloads = value;
+        self
+    }
+
+    /// Accept leading zeros in the integer part of decimal mantissa like `007.5`, enabled by
+    /// default. When disabled a digit after leading zero is an
+    /// [`InvalidCharacter`](crate::ParseFloatError::InvalidCharacter) error.
+    pub const fn leading_zeros(mut self, value: bool) -> Self {
+        self.leading_zeros = value;
+        self
+    }
+
+    /// Skip whitespace (space, `\t`, `\n`, `\v`, `\f`, `\r`) before the number.
+    pub const fn leading_whitespace(mut self, value: bool) -> Self {
+        self.leading_whitespace = value;
+        self
+    }
+
+    /// Skip whitespace after the number, count includes the skipped whitespace.
+    pub const fn trailing_whitespace(mut self, value: bool) -> Self {
+        self.trailing_whitespace = value;
+        self
+    }
+
+    /// Reject mantissas starting with the decimal separator, like `.5`.
+    pub const fn require_digits_before_point(mut self, value: bool) -> Self {
+        self.require_digits_before_point = value;
+        self
+    }
+
+    /// Reject mantissas ending with the decimal separator, like `5.` or `5.e3`,
+    /// reading stops before the separator.
+    pub const fn require_digits_after_point(mut self, value: bool) -> Self {
+        self.require_digits_after_point = value;
+        self
+    }
+
+    /// Chars starting the decimal exponent, `eE` by default. Empty disables the exponent.
+    pub const fn exponent_chars(mut self, value: &'static [u8]) -> Self {
+        self.exponent_chars = value;
+        self
+    }
+
+    /// Accept exponent with sign and without exponent char after the mantissa, like Fortran
+    /// `1.0+003`. Sign without digits after it is a dangling exponent.
+    pub const fn implicit_exponent(mut self, value: bool) -> Self {
+        self.implicit_exponent = value;
+        self
+    }
+
+    /// Accept Separator between digits of decimal mantissa and exponent, like `1_000_000.5`,
+    /// `1'000'000` or `1\u{2009}000` with thin space. Separator before or after the point,
+    /// at the start or the end of digits, or doubled is not a part of the number.
+    /// Should not be a digit, sign, decimal separator or exponent char.
+    pub const fn digit_separator(mut self, value: Option<char>) -> Self {
+        self.digit_separator = value;
+        self
+    }
+
+    /// Use decimal separator and digit grouping of Locale, numbers without grouping
+    /// are accepted too.
+    pub const fn locale(mut self, locale: Locale) -> Self {
+        self.decimal_separator = locale.decimal_separator;
+        self.grouping_separator = Some(locale.grouping_separator);
+        self
+    }
+}
+
+/// Spelling of infinity and NaN, used by [`ParseOptions::special_value_style`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecialValues {
+    /// No special values
+    None,
+    /// `inf`, `infinity` and `nan` in any register with optional sign
+    AnyCase,
+    /// `inf` and `nan` in lower case with optional sign, like TOML
+    LowerCase,
+    /// `.inf`, `.Inf`, `.INF` with optional sign and `.nan`, `.NaN`, `.NAN` without sign,
+    /// like YAML 1.2
+    Yaml,
+    /// Same as `AnyCase`, but partial `infinity` like `infin` reads as `inf`, like C `strtod`
+    C,
+}
+
+/// Decimal separator and thousands grouping of a language, used by [`ParseOptions::locale`]
+///
+/// Grouping separator is accepted only between digits of the integer part, splitting it
+/// into groups of three digits, the first group has one to three digits. On wrong size
+/// of a group reading stops before its separator, exact parsing reports an
+/// [`InvalidCharacter`](crate::ParseFloatError::InvalidCharacter) error at the char breaking
+/// the grouping.
+///
+/// ```
+/// use pure_parse_float::{parse_float_exact_with, Locale, ParseOptions};
+///
+/// let german = ParseOptions::new().locale(Locale::DE);
+/// assert_eq!(parse_float_exact_with(b"1.234.567,89", &german), Ok(1234567.89));
+/// assert_eq!(parse_float_exact_with(b"3,14", &german), Ok(3.14));
+/// assert!(parse_float_exact_with(b"1.23,5", &german).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Locale {
+    decimal_separator: u8,
+    grouping_separator: char,
+}
+
+impl Locale {
+    /// English, `1,234,567.89`
+    pub const EN: Locale = Locale::new(b'.', ',');
+    /// German, `1.234.567,89`
+    pub const DE: Locale = Locale::new(b',', '.');
+    /// French, `1\u{202F}234\u{202F}567,89` with narrow no-break space
+    pub const FR: Locale = Locale::new(b',', '\u{202F}');
+    /// Russian, `1\u{A0}234\u{A0}567,89` with no-break space
+    pub const RU: Locale = Locale::new(b',', '\u{A0}');
+
+    /// Locale with Decimal separator byte and Grouping separator char
+    pub const fn new(decimal_separator: u8, grouping_separator: char) -> Self {
+        Locale{
+            decimal_separator,
+            grouping_separator,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseOptions;
+    use crate::{parse_float_exact_with, parse_float_with, ParseFloatError};
+    use crate::random::{random_generator, EDGE_DECIMALS};
+
+    fn check_table(options: &ParseOptions, valid: &[(&str, f64)], invalid: &[&str]) {
+        for &(text, expected) in valid {
+            let value = parse_float_exact_with(text.as_bytes(), options).unwrap();
+            if expected.is_nan() {
+                assert!(value.is_nan(), "{text}");
+            } else {
+                assert_eq!(value.to_bits(), expected.to_bits(), "{text}");
+            }
+        }
+        for &text in invalid {
+            assert!(parse_float_exact_with(text.as_bytes(), options).is_err(), "{text}");
+        }
+    }
+
+    // Examples of https://toml.io/en/v1.0.0#float and #integer
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn toml() {
+        check_table(&ParseOptions::toml(), &[
+            ("+1.0", 1.0), ("3.1415", 3.1415), ("-0.01", -0.01),
+            ("5e+22", 5e22), ("1e06", 1e6), ("-2E-2", -0.02),
+            ("6.626e-34", 6.626e-34), ("224_617.445_991_228", 224617.445991228),
+            ("inf", f64::INFINITY), ("+inf", f64::INFINITY), ("-inf", f64::NEG_INFINITY),
+            ("nan", f64::NAN), ("+nan", f64::NAN), ("-nan", f64::NAN),
+            ("+99", 99.0), ("42", 42.0), ("0", 0.0), ("-17", -17.0), ("-0.0", -0.0), ("+0.0", 0.0),
+            ("1_000", 1000.0), ("5_349_221", 5349221.0), ("53_49_221", 5349221.0), ("1_2_3_4_5", 12345.0),
+        ], &[
+            ".7", "7.", "3.e+20", "01", "-00.5", "1__0", "_1", "1_", "1_.5", "1e_5",
+            "Inf", "infinity", "NaN", ".inf", "0x1p1", " 1",
+        ]);
+        assert_eq!(parse_float_exact_with(b"-007", &ParseOptions::toml()), Err(ParseFloatError::InvalidCharacter(2)));
+    }
+
+    // Examples of https://www.rfc-editor.org/rfc/rfc8259#section-6
+    #[test]
+    fn json() {
+        check_table(&ParseOptions::json(), &[
+            ("0", 0.0), ("-0", -0.0), ("-1.5", -1.5), ("3.141592653589793", std::f64::consts::PI),
+            ("1e+2", 100.0), ("2.5E-3", 0.0025), ("12e007", 12e7), ("0.0", 0.0),
+        ], &["+1", "01", "-00", ".5", "5.", "1.e1", "1e", "NaN", "Infinity", "-inf", "0x10", "1_000", " 1", "1.5.3"]);
+        assert_eq!(parse_float_with(b"1.5.3", &ParseOptions::json()), Some((1.5, 3)));
+    }
+
+    // Example 10.9 of https://yaml.org/spec/1.2.2/#1033-core-schema
+    #[test]
+    fn yaml() {
+        check_table(&ParseOptions::yaml(), &[
+            ("0", 0.0), ("-19", -19.0),
+            ("0.", 0.0), ("-0.0", -0.0), (".5", 0.5), ("+12e03", 12000.0), ("-2E+05", -200000.0),
+            (".inf", f64::INFINITY), ("-.Inf", f64::NEG_INFINITY), ("+.INF", f64::INFINITY), (".NAN", f64::NAN),
+            (".nan", f64::NAN), (".NaN", f64::NAN), ("007", 7.0),
+        ], &[
+            "0o7", "0x3A", "null", "true", "", ".", "inf", "-.nan", "+.NaN", ".iNF", "infinity", "1_000",
+        ]);
+    }
+
+    #[test]
+    fn fortran() {
+        let options = ParseOptions::fortran();
+        check_table(&options, &[
+            ("1.0D+03", 1e3), ("2.5Q-10", 2.5e-10), ("-1.5d2", -150.0), ("1q0", 1.0), ("1.0E5", 1e5),
+            ("1.0+003", 1e3), ("2.5-10", 2.5e-10), ("-.5+1", -5.0), ("1.+3", 1e3), ("7", 7.0),
+        ], &["1.0+", "1.0D", "1.0D+", "1.0+-3", "1.0D+0+3"]);
+        assert_eq!(parse_float_with(b"1.0+", &options), Some((1.0, 3)));
+        assert_eq!(parse_float_with(b"3-x", &options), Some((3.0, 1)));
+        assert_eq!(parse_float_exact_with(b"1.0-", &options), Err(ParseFloatError::DanglingExponent(3)));
+        assert_eq!(parse_float_with(b"1.0+003", &ParseOptions::new()), Some((1.0, 3)));
+        assert_eq!(parse_float_with(b"1.0D3", &ParseOptions::new()), Some((1.0, 3)));
+    }
+
+    #[test]
+    fn c() {
+        let options = ParseOptions::c();
+        check_table(&options, &[
+            (" \t1.5", 1.5), ("0x1p-2", 0.25), ("-INF", f64::NEG_INFINITY), ("Infinity", f64::INFINITY),
+            (".5e1", 5.0), ("1.", 1.0),
+        ], &["1.5 ", "infin", "snan", "0x", "1e", ".."]);
+        assert_eq!(parse_float_with(b"1..", &options), Some((1.0, 2)));
+        assert_eq!(parse_float_with(b"-..5", &options), None);
+        assert_eq!(parse_float_with(b"infin", &options), Some((f64::INFINITY, 3)));
+        assert_eq!(parse_float_with(b"snan", &options), None);
+        assert!(parse_float_with(b"-nan(7)", &options).is_some_and(|(v, n)| v.to_bits() == 0xFFF8_0000_0000_0007 && n == 7));
+        assert_eq!(parse_float_with(b"infin", &ParseOptions::new()), None);
+    }
+
+    // Grammar of https://doc.rust-lang.org/std/primitive.f64.html#method.from_str
+    #[test]
+    fn rust() {
+        let options = ParseOptions::rust();
+        let valid = [
+            ("inf", f64::INFINITY), ("+infinity", f64::INFINITY), ("-INFINITY", f64::NEG_INFINITY), ("iNf", f64::INFINITY),
+            ("NaN", f64::NAN), ("-nan", f64::NAN), ("+nan", f64::NAN), ("1.", 1.0), (".5", 0.5), ("+.5e-3", 0.0005),
+            ("1E+5", 1e5), ("1.e5", 1e5), ("007", 7.0), ("-0", -0.0), ("2.5e0", 2.5),
+        ];
+        let invalid = [
+            "1e", "_1", ".", "..", "", "e5", " 1", "1 ", "infin", "infinityx", "1_000", "0x10", "1.5.3", "+-1",
+            "-.e1", "nan(1)", "snan",
+        ];
+        check_table(&options, &valid, &invalid);
+        for (text, _) in valid {
+            assert!(text.parse::<f64>().is_ok(), "{text}");
+        }
+        for text in invalid {
+            assert!(text.parse::<f64>().is_err(), "{text}");
+        }
+        assert_eq!(parse_float_with(b"..", &options), None);
+        assert_eq!(parse_float_with(b"-.", &options), None);
+        assert_eq!(parse_float_with(b"1e", &options), Some((1.0, 1)));
+
+        // same texts are accepted, out of range values are infinity or zero
+        let mut texts: Vec<String> = Vec::new();
+        if cfg!(feature = "correct-rounding") {
+            texts.extend(EDGE_DECIMALS.map(String::from));
+        }
+        const ALPHABET: &[u8] = b"0123456789.eE+-infINFatyn_ x";
+        let mut next = random_generator(0x3C6EF372FE94F82B);
+        for _ in 0..20_000 {
+            let len = (next() % 12) as usize;
+            texts.push((0..len).map(|_| ALPHABET[(next() % ALPHABET.len() as u64) as usize] as char).collect());
+        }
+        for text in texts {
+            let result = match parse_float_exact_with(text.as_bytes(), &options) {
+            Ok(_) | Err(ParseFloatError::Overflow | ParseFloatError::Underflow) => {
+                parse_float_with(text.as_bytes(), &options).map(|(value, _)| value)
+            }
+            Err(_) => None,
+            };
+            let expected = text.parse::<f64>().ok();
+            assert_eq!(result.is_some(), expected.is_some(), "acceptance of {text:?}: {result:?} instead of {expected:?}");
+            if let (Some(result), Some(expected)) = (result, expected) {
+                if expected.is_nan() {
+                    assert!(result.is_nan(), "value of {text:?}: {result:e} instead of NaN");
+                } else {
+                    assert_eq!(result.to_bits(), expected.to_bits(), "value of {text:?}: {result:e} instead of {expected:e}");
+                }
+            }
+        }
+    }
+}
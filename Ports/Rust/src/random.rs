@@ -0,0 +1,34 @@
+//! Pseudorandom numbers for tests
+
+// Xorshift generator of 64 bit numbers, the same sequence for the same nonzero Seed
+pub(crate) fn random_generator(seed: u64) -> impl FnMut() -> u64 {
+    let mut state = seed;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    }
+}
+
+// Positive decimal text of 1 to 20 random digits with exponent from -290 to 309, where
+// Double-Double conversion is accurate in every build, edges of the range are EDGE_DECIMALS
+pub(crate) fn random_decimal(next: &mut dyn FnMut() -> u64) -> String {
+    let digits = 1 + next() % 20;
+    let mut text: String = (0..digits).map(|_| (b'0' + (next() % 10) as u8) as char).collect();
+    text.push_str(&format!("e{}", (next() % 600) as i64 - 290));
+    text
+}
+
+// Decimal texts at the edges of conversion to Double: halfway between Doubles and near it,
+// around the min normal and the min subnormal Double, around the max Double and overflow.
+// Without the `correct-rounding` feature some of them are converted with error of 1 ULP
+pub(crate) const EDGE_DECIMALS: [&str; 24] = [
+    "9007199254740993", "9007199254740995", "-9007199254740993.0000000000000001", "9007199254740992.9999999999999999",
+    "1.00000000000000011102230246251565404236316680908203125", "1.00000000000000011102230246251565404236316680908203126",
+    "1.00000000000000033306690738754696212708950042724609375", "1e23",
+    "2.2250738585072009e-308", "2.2250738585072011e-308", "2.2250738585072012e-308", "2.2250738585072014e-308",
+    "4.9406564584124654e-324", "7.4109846876186982e-324", "2.4703282292062327e-324", "2.4703282292062328e-324",
+    "-1e-400", "1.7976931348623157e308", "1.7976931348623158e308", "1.7976931348623159e308", "-1.797693134862315807e308",
+    "8.98846567431158e307", "1e309", "1e-99999999999999999999",
+];
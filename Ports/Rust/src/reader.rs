@@ -0,0 +1,265 @@
+//! Text scanners used by the parser, selected with the `pointer-scanner` feature
+
+use std::marker::PhantomData;
+
+// Code unit of UTF-8, UTF-16 or UTF-32 text
+pub(crate) trait CodeUnit: Copy + Default + Eq + 'static {
+    // Byte used by the pattern, code units out of ASCII are 0xFF, which is never a part of
+    // the pattern, bytes of UTF-8 are unchanged
+    fn to_ascii(self) -> u8;
+
+    // Code units of C in this encoding
+    fn encode(c: char, buffer: &mut [Self; 4]) -> &[Self];
+
+    // First char of Text and its length in code units, invalid code unit is None of length 1,
+    // empty text is None of length 0
+    fn decode(text: &[Self]) -> (Option<char>, usize);
+}
+
+impl CodeUnit for u8 {
+    fn to_ascii(self) -> u8 {
+        self
+    }
+
+    fn encode(c: char, buffer: &mut [Self; 4]) -> &[Self] {
+        c.encode_utf8(buffer).as_bytes()
+    }
+
+    fn decode(text: &[Self]) -> (Option<char>, usize) {
+        let len = match text.first() {
+        None => return (None, 0),
+        Some(0x00..=0x7F) => 1,
+        Some(0xC2..=0xDF) => 2,
+        Some(0xE0..=0xEF) => 3,
+        Some(0xF0..=0xF4) => 4,
+        Some(_) => return (None, 1),
+        };
+        match text.get(..len).map(std::str::from_utf8) {
+        Some(Ok(c)) => (c.chars().next(), len),
+        _ => (None, 1),
+        }
+    }
+}
+
+impl CodeUnit for u16 {
+    fn to_ascii(self) -> u8 {
+        if self < 0x80 { self as u8 } else { 0xFF }
+    }
+
+    fn encode(c: char, buffer: &mut [Self; 4]) -> &[Self] {
+        let mut units = [0; 2];
+        let len = c.encode_utf16(&mut units).len();
+        buffer[..len].copy_from_slice(&units[..len]);
+        &buffer[..len]
+    }
+
+    fn decode(text: &[Self]) -> (Option<char>, usize) {
+        match char::decode_utf16(text.iter().copied()).next() {
+        None => (None, 0),
+        Some(Ok(c)) => (Some(c), c.len_utf16()),
+        Some(Err(_)) => (None, 1),
+        }
+    }
+}
+
+impl CodeUnit for u32 {
+    fn to_ascii(self) -> u8 {
+        if self < 0x80 { self as u8 } else { 0xFF }
+    }
+
+    fn encode(c: char, buffer: &mut [Self; 4]) -> &[Self] {
+        buffer[0] = c as u32;
+        &buffer[..1]
+    }
+
+    fn decode(text: &[Self]) -> (Option<char>, usize) {
+        match text.first() {
+        None => (None, 0),
+        Some(&unit) => (char::from_u32(unit), 1),
+        }
+    }
+}
+
+// Cursor over text, yields 0 past the end like a null terminated string
+pub(crate) trait Reader<'a>: Clone {
+    type Unit: CodeUnit;
+
+    // code unit at the reader position as byte of the pattern, see CodeUnit::to_ascii
+    fn get(&self) -> u8 {
+        self.unit().map_or(0, CodeUnit::to_ascii)
+    }
+
+    // same as get, but Unicode chars are never mapped to ASCII, for hexadecimal floats
+    // and NaN payloads, which are ASCII only
+    fn get_ascii(&self) -> u8 {
+        self.get()
+    }
+
+    // code unit at the reader position, None past the end
+    fn unit(&self) -> Option<Self::Unit>;
+
+    // before using, make sure Reader is not ended
+    fn advance(&mut self);
+
+    fn ended(&self) -> bool;
+
+    // count of code units read from start
+    fn position(&self) -> usize;
+
+    // ASCII Text is at the reader position
+    fn starts_with(&self, text: &[u8]) -> bool {
+        let mut p = self.clone();
+        for &c in text {
+            if p.ended() || p.get() != c {
+                return false;
+            }
+            p.advance();
+        }
+        true
+    }
+
+    // Skip Separator only if it is followed by a digit, None is never skipped
+    fn skip_digit_separator(&mut self, separator: Option<char>) -> bool {
+        let Some(separator) = separator else {
+            return false;
+        };
+        let mut buffer = [Self::Unit::default(); 4];
+        let mut p = self.clone();
+        for &unit in Self::Unit::encode(separator, &mut buffer) {
+            if p.unit() != Some(unit) {
+                return false;
+            }
+            p.advance();
+        }
+        if !p.get().is_ascii_digit() {
+            return false;
+        }
+        *self = p;
+        true
+    }
+}
+
+#[cfg(not(feature = "pointer-scanner"))]
+pub(crate) type TextReader<'a, T = u8> = SliceReader<'a, T>;
+#[cfg(feature = "pointer-scanner")]
+pub(crate) type TextReader<'a, T = u8> = PointerReader<'a, T>;
+
+// Bounds checked reader over a slice of code units
+#[derive(Clone)]
+#[cfg_attr(feature = "pointer-scanner", allow(dead_code))]
+pub(crate) struct SliceReader<'a, T = u8>(&'a [T], usize);
+
+#[cfg_attr(feature = "pointer-scanner", allow(dead_code))]
+impl<'a, T: CodeUnit> SliceReader<'a, T> {
+    pub(crate) fn new(text: &'a [T]) -> Self {
+        SliceReader(text, 0)
+    }
+}
+
+impl<'a, T: CodeUnit> Reader<'a> for SliceReader<'a, T> {
+    type Unit = T;
+
+    fn unit(&self) -> Option<T> {
+        self.0.get(self.1).copied()
+    }
+
+    fn advance(&mut self) {
+        self.1 += 1;
+    }
+
+    fn ended(&self) -> bool {
+        self.1 >= self.0.len()
+    }
+
+    fn position(&self) -> usize {
+        self.1
+    }
+}
+
+// Raw pointer reader, compares with the end pointer instead of checking slice bounds
+#[derive(Clone)]
+#[cfg_attr(not(feature = "pointer-scanner"), allow(dead_code))]
+pub(crate) struct PointerReader<'a, T = u8> {
+    start: *const T,
+    p: *const T,
+    end: *const T,
+    text: PhantomData<&'a [T]>,
+}
+
+#[cfg_attr(not(feature = "pointer-scanner"), allow(dead_code))]
+impl<'a, T: CodeUnit> PointerReader<'a, T> {
+    pub(crate) fn new(text: &'a [T]) -> Self {
+        let range = text.as_ptr_range();
+        PointerReader{
+            start: range.start,
+            p: range.start,
+            end: range.end,
+            text: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: CodeUnit> Reader<'a> for PointerReader<'a, T> {
+    type Unit = T;
+
+    fn unit(&self) -> Option<T> {
+        // p always stays inside start..=end of the borrowed slice
+        if self.p < self.end { Some(unsafe { *self.p }) } else { None }
+    }
+
+    fn advance(&mut self) {
+        self.p = self.p.wrapping_add(1);
+    }
+
+    fn ended(&self) -> bool {
+        self.p >= self.end
+    }
+
+    fn position(&self) -> usize {
+        (self.p as usize - self.start as usize) / std::mem::size_of::<T>()
+    }
+}
+
+// Reader of null terminated text from C, the terminator is the end of text, so the text
+// is read only as far as the pattern goes, without measuring its length first
+#[derive(Clone)]
+pub(crate) struct NulReader<'a, T = u8> {
+    start: *const T,
+    p: *const T,
+    text: PhantomData<&'a T>,
+}
+
+impl<'a, T: CodeUnit> NulReader<'a, T> {
+    // Text must be null terminated and live for 'a
+    pub(crate) unsafe fn new(text: *const T) -> Self {
+        NulReader{
+            start: text,
+            p: text,
+            text: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: CodeUnit> Reader<'a> for NulReader<'a, T> {
+    type Unit = T;
+
+    fn unit(&self) -> Option<T> {
+        // p never moves past the terminator
+        let unit = unsafe { *self.p };
+        (unit != T::default()).then_some(unit)
+    }
+
+    // the terminator is checked only when the unit is read, callers advance past read
+    // units of the pattern, which are never the terminator
+    fn advance(&mut self) {
+        self.p = self.p.wrapping_add(1);
+    }
+
+    fn ended(&self) -> bool {
+        self.unit().is_none()
+    }
+
+    fn position(&self) -> usize {
+        (self.p as usize - self.start as usize) / std::mem::size_of::<T>()
+    }
+}
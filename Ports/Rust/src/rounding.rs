@@ -0,0 +1,372 @@
+//! Conversion with rounding modes of IEEE 754, done in software on top of Double-Double,
+//! so the result does not depend on the rounding mode of the FPU
+
+use std::cmp::Ordering;
+
+use crate::double_double::DoubleDouble;
+use crate::fixed_decimal::FixedDecimal;
+use crate::reader::TextReader;
+use crate::correct_rounding::{BinaryFloat, HALFWAY_TOLERANCE, MIN_ACCURATE_DOUBLE};
+use crate::{read_number, Number, ParseOptions};
+
+/// Rounding of the decimal value to Double, used by [`parse_float_rounded`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Nearest Double, ties to even mantissa, same as [`parse_float`](crate::parse_float)
+    NearestTiesToEven,
+    /// Nearest Double, ties away from zero
+    NearestTiesToAway,
+    /// Toward zero, overflow gives the max finite Double
+    TowardZero,
+    /// Toward positive infinity
+    TowardPositive,
+    /// Toward negative infinity
+    TowardNegative,
+}
+
+/// Doubles enclosing the exact decimal value, returned by [`parse_float_interval`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatInterval {
+    /// Largest Double not above the value, negative infinity stands below the min Double
+    pub lo: f64,
+    /// Smallest Double not below the value, infinity stands above the max Double
+    pub hi: f64,
+    /// The value is a Double, `lo == hi`, Inf and NaN are exact
+    pub is_exact: bool,
+}
+
+impl FixedDecimal {
+    /// Convert to the interval of Doubles enclosing the decimal value, see
+    /// [`parse_float_interval`].
+    pub fn to_f64_interval(&self) -> FloatInterval {
+        let nearest = self.to_f64().abs();
+        let number = self.to_double_double();
+        let down = self.round_toward(number, nearest, false);
+        let up = if self.compare_double_double(number, down, false) == Ordering::Equal {
+            down
+        } else {
+            self.round_toward(number, nearest, true)
+        };
+        let (lo, hi) = if self.is_negative() { (-up, -down) } else { (down, up) };
+        FloatInterval{
+            lo,
+            hi,
+            is_exact: down == up,
+        }
+    }
+
+    /// Convert to Double with the rounding Mode, the FPU rounding mode is not used.
+    ///
+    /// Directed modes are correctly rounded, when the Double-Double result is too close to
+    /// a Double it is compared with the digits exactly. When digits were dropped, see
+    /// [`is_truncated`](FixedDecimal::is_truncated), the result can be one Double further from
+    /// the value, but never on the wrong side of it. Nearest modes have the accuracy of
+    /// [`to_f64`](FixedDecimal::to_f64).
+    pub fn to_f64_rounded(&self, mode: RoundingMode) -> f64 {
+        let nearest = self.to_f64().abs();
+        let number = self.to_double_double();
+
+        let res = match mode {
+        RoundingMode::NearestTiesToEven => nearest,
+        // tie rounded to even Double below is moved up
+        RoundingMode::NearestTiesToAway => match self.compare_double_double(number, nearest, true) {
+            Ordering::Equal => nearest.next_up(),
+            _ => nearest,
+            },
+        RoundingMode::TowardZero => self.round_toward(number, nearest, false),
+        RoundingMode::TowardPositive => self.round_toward(number, nearest, !self.is_negative()),
+        RoundingMode::TowardNegative => self.round_toward(number, nearest, self.is_negative()),
+        };
+        if self.is_negative() { -res } else { res }
+    }
+
+    // Round absolute value down or up, starting from the Nearest Double,
+    // which can be one Double off without correct-rounding
+    fn round_toward(&self, number: DoubleDouble, nearest: f64, is_up: bool) -> f64 {
+        // dropped digits are below one unit of the last digit
+        if is_up && self.is_truncated() {
+            let upper = self.last_digit_up();
+            return upper.round_toward(upper.to_double_double(), nearest, true);
+        }
+
+        let mut value = nearest;
+        if is_up {
+            while self.compare_double_double(number, value, false) == Ordering::Greater {
+                value = value.next_up();
+            }
+            while value != 0.0 && self.compare_double_double(number, value.next_down(), false) != Ordering::Greater {
+                value = value.next_down();
+            }
+        } else {
+            while self.compare_double_double(number, value, false) == Ordering::Less {
+                value = value.next_down();
+            }
+            while !value.is_infinite() && self.compare_double_double(number, value.next_up(), false) != Ordering::Less {
+                value = value.next_up();
+            }
+        }
+        value
+    }
+
+    // Compare absolute value of decimal with Value, or with the midpoint between Value and the
+    // next Double up, using Double-Double conversion Number.
+    // When Number is too close or out of accurate range, the comparison is exact.
+    pub(crate) fn compare_double_double(&self, number: DoubleDouble, value: f64, is_midpoint: bool) -> Ordering {
+        if self.digits().is_empty() {
+            return if value == 0.0 && !is_midpoint { Ordering::Equal } else { Ordering::Less };
+        }
+        if value.is_infinite() {
+            return Ordering::Less;
+        }
+        if value == 0.0 && !is_midpoint {
+            return Ordering::Greater;
+        }
+        // out of range decimal is below the half of the min Double or above the max Double,
+        // exact comparison of it would take the time of the exponent
+        if !self.is_in_range() {
+            return if self.exponent() < 0 { Ordering::Less } else { Ordering::Greater };
+        }
+
+        // doubled distance from the target, exact for values near it
+        let ulp = value.next_up() - value;
+        let target = if is_midpoint { ulp } else { 0.0 };
+        let residual = 2.0 * ((number.hi - value) + number.lo) - target;
+
+        if residual.abs() <= ulp * HALFWAY_TOLERANCE || value < MIN_ACCURATE_DOUBLE || ulp.is_infinite() {
+            if is_midpoint {
+                return self.compare_midpoint(value, value.next_up());
+            }
+            let (mantissa, exponent) = value.parts();
+            return self.compare(mantissa, exponent);
+        }
+
+        match residual.partial_cmp(&0.0) {
+        // dropped digits make decimal a bit larger
+        Some(Ordering::Equal) if self.is_truncated() => Ordering::Greater,
+        Some(ordering) => ordering,
+        None => Ordering::Less,
+        }
+    }
+}
+
+/// Same as [`parse_float`](crate::parse_float), but rounds with the Mode instead of to nearest.
+///
+/// Rounding is done in software, the result does not depend on the rounding mode of the FPU.
+/// Lower and upper bounds of the decimal value are given by
+/// [`TowardNegative`](RoundingMode::TowardNegative) and
+/// [`TowardPositive`](RoundingMode::TowardPositive).
+///
+/// ```
+/// use pure_parse_float::{parse_float_rounded, RoundingMode};
+///
+/// let (lower, _) = parse_float_rounded(b"0.1", RoundingMode::TowardNegative).unwrap();
+/// let (upper, _) = parse_float_rounded(b"0.1", RoundingMode::TowardPositive).unwrap();
+/// assert_eq!((lower.next_up(), upper), (0.1, 0.1));
+/// assert_eq!(parse_float_rounded(b"1e999", RoundingMode::TowardZero), Some((f64::MAX, 5)));
+/// ```
+pub fn parse_float_rounded(text: &[u8], mode: RoundingMode) -> Option<(f64, usize)> {
+    let (number, count) = read_number(TextReader::new(text), &ParseOptions::new(), false).ok()?;
+    let res = match number {
+    Number::Decimal(decimal) => decimal.to_f64_rounded(mode),
+    // Inf and NaN are exact
+    number => number.to_f64(),
+    };
+    Some((res, count))
+}
+
+/// Parse float from the start of bytes to the interval of Doubles enclosing the exact
+/// decimal value, with `lo == hi` when the value is a Double.
+///
+/// Grammar is the same as of [`parse_float`](crate::parse_float). The bounds are the same as
+/// [`parse_float_rounded`] with [`TowardNegative`](RoundingMode::TowardNegative) and
+/// [`TowardPositive`](RoundingMode::TowardPositive), they are found from the remainder `lo`
+/// of the Double-Double conversion, with exact comparison of digits when it is too small.
+///
+/// ```
+/// use pure_parse_float::{parse_float_interval, FloatInterval};
+///
+/// let (interval, count) = parse_float_interval(b"0.1").unwrap();
+/// assert_eq!(count, 3);
+/// assert_eq!((interval.lo, interval.hi, interval.is_exact), (0.1f64.next_down(), 0.1, false));
+/// let (interval, _) = parse_float_interval(b"-0.375").unwrap();
+/// assert_eq!(interval, FloatInterval{ lo: -0.375, hi: -0.375, is_exact: true });
+/// ```
+pub fn parse_float_interval(text: &[u8]) -> Option<(FloatInterval, usize)> {
+    let (number, count) = read_number(TextReader::new(text), &ParseOptions::new(), false).ok()?;
+    let interval = match number {
+    Number::Decimal(decimal) => decimal.to_f64_interval(),
+    // Inf and NaN are exact
+    number => {
+        let value = number.to_f64();
+        FloatInterval{ lo: value, hi: value, is_exact: true }
+    }
+    };
+    Some((interval, count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_float_interval, parse_float_rounded, RoundingMode};
+    use crate::parse_float;
+    use crate::random::{random_decimal, random_generator, EDGE_DECIMALS};
+
+    const MODES: [RoundingMode; 5] = [
+        RoundingMode::NearestTiesToEven, RoundingMode::NearestTiesToAway, RoundingMode::TowardZero,
+        RoundingMode::TowardPositive, RoundingMode::TowardNegative,
+    ];
+
+    fn rounded(text: &str) -> [u64; 5] {
+        MODES.map(|mode| {
+            let (value, count) = parse_float_rounded(text.as_bytes(), mode).unwrap();
+            assert_eq!(count, text.len(), "{text}");
+            value.to_bits()
+        })
+    }
+
+    #[test]
+    fn modes() {
+        let bits = |values: [f64; 5]| values.map(f64::to_bits);
+        let below = 0.1f64.next_down();
+        let max = f64::MAX;
+        let inf = f64::INFINITY;
+        let min = 5e-324;
+        // nearest even, nearest away, zero, positive, negative
+        for (text, expected) in [
+            ("1.5", [1.5; 5]), ("-0", [-0.0; 5]), ("0e10", [0.0; 5]), ("inf", [inf; 5]),
+            ("0.1", [0.1, 0.1, below, 0.1, below]), ("-0.1", [-0.1, -0.1, -below, -below, -0.1]),
+            ("1e400", [inf, inf, max, inf, max]), ("-1e400", [-inf, -inf, -max, -max, -inf]),
+            ("1e-400", [0.0, 0.0, 0.0, min, 0.0]), ("-1e-400", [-0.0, -0.0, -0.0, -0.0, -min]),
+            ("5e-324", [min, min, min, 2.0 * min, min]), ("4.9406564584124654e-324", [min, min, 0.0, min, 0.0]), ("3e-324", [min, min, 0.0, min, 0.0]),
+            ("2.4703282292062327e-324", [0.0, 0.0, 0.0, min, 0.0]),
+            // exponents out of range are not compared exactly
+            ("1e999999", [inf, inf, max, inf, max]), ("-1e1000000", [-inf, -inf, -max, -max, -inf]),
+            ("1e-1000000", [0.0, 0.0, 0.0, min, 0.0]), ("-1e-999999", [-0.0, -0.0, -0.0, -0.0, -min]),
+            ("1.00000000000000000000000000000000000001e-999999", [0.0, 0.0, 0.0, min, 0.0]),
+            ("9.99999999999999999999999999999999999999e999999", [inf, inf, max, inf, max]),
+            ("9007199254740993", [9007199254740992.0, 9007199254740994.0, 9007199254740992.0, 9007199254740994.0, 9007199254740992.0]),
+            ("-9007199254740995", [-9007199254740996.0, -9007199254740996.0, -9007199254740994.0, -9007199254740994.0, -9007199254740996.0]),
+            ("9007199254740992.99999999999999999999999999999999999999", [9007199254740992.0, 9007199254740992.0, 9007199254740992.0, 9007199254740994.0, 9007199254740992.0]),
+        ] {
+            assert_eq!(rounded(text), bits(expected), "{text}");
+        }
+        // directed modes are exact for digits dropped without correct-rounding
+        let [_, _, zero, up, down] = rounded("9007199254740993.00000000000000000000000000000000000001");
+        assert_eq!([zero, up, down].map(f64::from_bits), [9007199254740992.0, 9007199254740994.0, 9007199254740992.0]);
+        assert_eq!(parse_float_rounded(b"nan", RoundingMode::TowardZero).map(|(v, n)| (v.is_nan(), n)), Some((true, 3)));
+        assert_eq!(parse_float_rounded(b"x", RoundingMode::TowardZero), None);
+    }
+
+    #[test]
+    fn bounds() {
+        let mut texts: Vec<String> = Vec::new();
+        if cfg!(feature = "correct-rounding") {
+            texts.extend(EDGE_DECIMALS.map(|text| text.trim_start_matches('-').to_string()));
+        }
+        let mut next = random_generator(0x510E527FADE682D1);
+        texts.extend((0..10_000).map(|_| random_decimal(&mut next)));
+
+        // nearest is one of the enclosing Doubles, away from zero is the same or the other one
+        for text in texts {
+            let [even, away, zero, up, down] = rounded(&text).map(f64::from_bits);
+            let nearest = parse_float(text.as_bytes()).unwrap().0;
+            assert_eq!(even.to_bits(), nearest.to_bits(), "ties to even of {text}: {even:e} instead of {nearest:e}");
+            assert_eq!(zero.to_bits(), down.to_bits(), "toward zero of {text}: {zero:e} instead of {down:e}");
+            assert!(away == even || away == up, "away from zero of {text}: {away:e}, even {even:e}, up {up:e}");
+            assert!(down <= nearest && nearest <= up, "enclosing of {text}: {nearest:e} out of {down:e} .. {up:e}");
+            assert!(up == down || up == down.next_up(), "neighbours of {text}: {down:e} and {up:e}");
+        }
+    }
+
+    #[test]
+    fn intervals() {
+        let interval = |text: &str| {
+            let (interval, count) = parse_float_interval(text.as_bytes()).unwrap();
+            assert_eq!(count, text.len(), "{text}");
+            (interval.lo.to_bits(), interval.hi.to_bits(), interval.is_exact)
+        };
+        let min = 5e-324;
+        for (text, lo, hi, is_exact) in [
+            ("1.5", 1.5, 1.5, true), ("-0", -0.0, -0.0, true), ("0.1", 0.1f64.next_down(), 0.1, false),
+            ("-0.1", -0.1, -0.1f64.next_down(), false), ("1e400", f64::MAX, f64::INFINITY, false),
+            ("-1e400", f64::NEG_INFINITY, -f64::MAX, false), ("1e-400", 0.0, min, false),
+            ("-1e-400", -min, -0.0, false), ("-inf", f64::NEG_INFINITY, f64::NEG_INFINITY, true),
+            ("9007199254740993", 9007199254740992.0, 9007199254740994.0, false),
+            ("1e999999", f64::MAX, f64::INFINITY, false), ("-1e1000000", f64::NEG_INFINITY, -f64::MAX, false),
+            ("1e-999999", 0.0, min, false), ("-1e-1000000", -min, -0.0, false),
+        ] {
+            assert_eq!(interval(text), (lo.to_bits(), hi.to_bits(), is_exact), "{text}");
+        }
+        let (nan, _) = parse_float_interval(b"nan").unwrap();
+        assert!(nan.lo.is_nan() && nan.hi.is_nan() && nan.is_exact);
+        assert_eq!(parse_float_interval(b"-"), None);
+
+        let mut next = random_generator(0x1F83D9ABFB41BD6B);
+        for i in 0..2000 {
+            let bits = match i % 2 {
+            0 => next() % 0x7FF0_0000_0000_0000,
+            _ => next() % 0x0020_0000_0000_0000, // denormals and near them
+            };
+            let value = f64::from_bits(bits);
+            let exact = format!("{:.1100}", value).trim_end_matches('0').to_string();
+            let above = format!("{exact}00000000000000000000000000000000000000000001");
+            let significant_digits = exact.replace('.', "").trim_matches('0').len();
+            if cfg!(feature = "correct-rounding") || significant_digits <= 34 {
+                assert_eq!(interval(&exact), (bits, bits, true), "{exact}");
+                assert_eq!(interval(&above), (bits, value.next_up().to_bits(), false), "{above}");
+                continue;
+            }
+
+            // dropped digits give wider intervals, which still enclose the value
+            for text in [&exact, &above] {
+                let (interval, _) = parse_float_interval(text.as_bytes()).unwrap();
+                let upper = if text == &exact { value } else { value.next_up() };
+                assert!(interval.lo <= value && upper <= interval.hi && !interval.is_exact, "{text}");
+                assert!(value.next_down() <= interval.lo && interval.hi <= upper.next_up(), "{text}");
+            }
+        }
+    }
+
+    // glibc strtod follows the FPU rounding mode, only used here to check the results
+    #[cfg(all(unix, target_env = "gnu", target_arch = "x86_64"))]
+    #[test]
+    fn same_as_strtod() {
+        use std::ffi::{c_char, c_int, CString};
+
+        extern "C" {
+            fn strtod(text: *const c_char, text_end: *mut *mut c_char) -> f64;
+            fn fesetround(mode: c_int) -> c_int;
+        }
+        const FE_TONEAREST: c_int = 0;
+        const FE_DOWNWARD: c_int = 0x400;
+        const FE_UPWARD: c_int = 0x800;
+        const FE_TOWARDZERO: c_int = 0xC00;
+
+        let mut texts: Vec<String> = Vec::new();
+        if cfg!(feature = "correct-rounding") {
+            texts.extend(EDGE_DECIMALS.map(String::from));
+        }
+        let mut next = random_generator(0x9B05688C2B3E6C1F);
+        for _ in 0..10_000 {
+            let sign = if next().is_multiple_of(2) { "-" } else { "" };
+            texts.push(format!("{sign}{}", random_decimal(&mut next)));
+        }
+
+        let modes = [
+            (FE_TONEAREST, RoundingMode::NearestTiesToEven), (FE_TOWARDZERO, RoundingMode::TowardZero),
+            (FE_UPWARD, RoundingMode::TowardPositive), (FE_DOWNWARD, RoundingMode::TowardNegative),
+        ];
+        for text in texts {
+            let c_text = CString::new(text.clone()).unwrap();
+            for (fe_mode, mode) in modes {
+                let expected = unsafe {
+                    fesetround(fe_mode);
+                    let value = strtod(c_text.as_ptr(), std::ptr::null_mut());
+                    fesetround(FE_TONEAREST);
+                    value
+                };
+                let value = parse_float_rounded(text.as_bytes(), mode).unwrap().0;
+                assert_eq!(value.to_bits(), expected.to_bits(), "{mode:?} of {text}: {value:e} instead of {expected:e}");
+            }
+        }
+    }
+}
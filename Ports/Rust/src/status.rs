@@ -0,0 +1,173 @@
+//! Status flags of conversion, like IEEE 754 exceptions
+
+use std::cmp::Ordering;
+use std::ops::{BitOr, BitOrAssign};
+
+use crate::fixed_decimal::FixedDecimal;
+use crate::reader::TextReader;
+use crate::{read_number, Number, ParseOptions};
+
+/// Set of IEEE 754 status flags of conversion, returned by [`parse_float_status`]
+///
+/// ```
+/// use pure_parse_float::FloatStatus;
+///
+/// let status = FloatStatus::OVERFLOW | FloatStatus::INEXACT;
+/// assert!(status.contains(FloatStatus::INEXACT));
+/// assert!(!status.contains(FloatStatus::UNDERFLOW));
+/// assert_eq!(status.bits(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FloatStatus(u8);
+
+impl FloatStatus {
+    /// Result is not equal to the decimal value
+    pub const INEXACT: FloatStatus = FloatStatus(1);
+    /// Finite decimal value is rounded to infinity
+    pub const OVERFLOW: FloatStatus = FloatStatus(2);
+    /// Inexact result of a tiny value, which is below the min normal Double after rounding
+    /// to 53 bits with unbounded exponent (IEEE 754 tininess after rounding)
+    pub const UNDERFLOW: FloatStatus = FloatStatus(4);
+    /// Result is subnormal
+    pub const SUBNORMAL: FloatStatus = FloatStatus(8);
+
+    /// No flags, exact conversion to a normal Double, zero, Inf or NaN
+    pub const fn empty() -> Self {
+        FloatStatus(0)
+    }
+
+    /// Flags as bits, values of the constants
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// No flags are set
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// All flags of Other are set
+    pub const fn contains(self, other: FloatStatus) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for FloatStatus {
+    type Output = FloatStatus;
+
+    fn bitor(self, rhs: FloatStatus) -> FloatStatus {
+        FloatStatus(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for FloatStatus {
+    fn bitor_assign(&mut self, rhs: FloatStatus) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl FixedDecimal {
+    /// Convert to nearest Double like [`to_f64`](FixedDecimal::to_f64), with status flags
+    /// of the conversion, see [`parse_float_status`].
+    pub fn to_f64_status(&self) -> (f64, FloatStatus) {
+        let value = self.to_f64();
+        let abs = value.abs();
+
+        let mut status = FloatStatus::empty();
+        if self.compare_double_double(self.to_double_double(), abs, false) != Ordering::Equal {
+            status |= FloatStatus::INEXACT;
+        }
+        // decimal is always finite, exponent is clipped while reading
+        if abs.is_infinite() {
+            status |= FloatStatus::OVERFLOW;
+        }
+        if abs != 0.0 && abs < f64::MIN_POSITIVE {
+            status |= FloatStatus::SUBNORMAL;
+        }
+        if self.is_tiny(abs) && status.contains(FloatStatus::INEXACT) {
+            status |= FloatStatus::UNDERFLOW;
+        }
+        (value, status)
+    }
+}
+
+/// Same as [`parse_float`](crate::parse_float), with IEEE 754 status flags of the conversion.
+///
+/// [`INEXACT`](FloatStatus::INEXACT) is found from the remainder `lo` of the Double-Double
+/// conversion, with exact comparison of digits when it is too small. Without the
+/// `correct-rounding` feature a value with more than 34 significant digits is always inexact.
+/// Tininess of [`UNDERFLOW`](FloatStatus::UNDERFLOW) is detected after rounding like SSE
+/// and glibc `strtod` on x86-64, so a value rounded up to the min normal Double can underflow.
+/// Inf and NaN have no flags.
+///
+/// ```
+/// use pure_parse_float::{parse_float_status, FloatStatus};
+///
+/// assert_eq!(parse_float_status(b"0.5"), Some((0.5, FloatStatus::empty(), 3)));
+/// assert_eq!(parse_float_status(b"0.1"), Some((0.1, FloatStatus::INEXACT, 3)));
+/// assert_eq!(parse_float_status(b"1e400"), Some((f64::INFINITY, FloatStatus::OVERFLOW | FloatStatus::INEXACT, 5)));
+/// assert_eq!(parse_float_status(b"1e-400"), Some((0.0, FloatStatus::UNDERFLOW | FloatStatus::INEXACT, 6)));
+/// ```
+pub fn parse_float_status(text: &[u8]) -> Option<(f64, FloatStatus, usize)> {
+    let (number, count) = read_number(TextReader::new(text), &ParseOptions::new(), false).ok()?;
+    let (value, status) = match number {
+    Number::Decimal(decimal) => decimal.to_f64_status(),
+    number => (number.to_f64(), FloatStatus::empty()),
+    };
+    Some((value, status, count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_float_status, FloatStatus};
+    use crate::{parse_float, parse_float_interval};
+    use crate::random::{random_decimal, random_generator, EDGE_DECIMALS};
+
+    #[test]
+    fn flags() {
+        const INEXACT: u8 = FloatStatus::INEXACT.bits();
+        const OVERFLOW: u8 = FloatStatus::OVERFLOW.bits();
+        const UNDERFLOW: u8 = FloatStatus::UNDERFLOW.bits();
+        const SUBNORMAL: u8 = FloatStatus::SUBNORMAL.bits();
+        // exact subnormal has 751 significant digits, they are truncated without correct rounding
+        let subnormal = format!("{:.1100}", 3.0 * 5e-324);
+        let exact_subnormal = if cfg!(feature = "correct-rounding") { SUBNORMAL } else { SUBNORMAL | UNDERFLOW | INEXACT };
+        for (text, expected) in [
+            ("1.5", 0), ("-0", 0), ("0e-999999999", 0), ("inf", 0), ("nan", 0), ("9007199254740992", 0),
+            ("0.1", INEXACT), ("-9007199254740993", INEXACT), ("2.2250738585072014e-308", INEXACT),
+            ("1e400", OVERFLOW | INEXACT), ("-1e99999999999999999999", OVERFLOW | INEXACT),
+            ("1e-400", UNDERFLOW | INEXACT), ("-1e-99999999999999999999", UNDERFLOW | INEXACT),
+            ("5e-324", SUBNORMAL | UNDERFLOW | INEXACT), ("2.2250738585072e-308", SUBNORMAL | UNDERFLOW | INEXACT),
+            // tininess after rounding, the last two round to the min normal Double
+            ("2.2250738585072011e-308", SUBNORMAL | UNDERFLOW | INEXACT),
+            ("2.2250738585072012e-308", UNDERFLOW | INEXACT), ("2.22507385850720132e-308", INEXACT),
+            (&subnormal, exact_subnormal),
+        ] {
+            let (value, status, count) = parse_float_status(text.as_bytes()).unwrap();
+            assert_eq!((status.bits(), count), (expected, text.len()), "{text}");
+            assert_eq!(value.to_bits(), parse_float(text.as_bytes()).unwrap().0.to_bits(), "{text}");
+        }
+        assert_eq!(parse_float_status(b"e5"), None);
+
+        let mut status = FloatStatus::empty();
+        assert!(status.is_empty() && status.contains(FloatStatus::empty()));
+        status |= FloatStatus::SUBNORMAL;
+        assert!(!status.is_empty() && status.contains(FloatStatus::SUBNORMAL));
+        assert!(!status.contains(FloatStatus::SUBNORMAL | FloatStatus::INEXACT));
+    }
+
+    #[test]
+    fn same_as_interval() {
+        let mut texts: Vec<String> = EDGE_DECIMALS.map(String::from).to_vec();
+        let mut next = random_generator(0x5BE0CD19137E2179);
+        texts.extend((0..10_000).map(|_| random_decimal(&mut next)));
+
+        for text in texts {
+            let (value, status, _) = parse_float_status(text.as_bytes()).unwrap();
+            let (interval, _) = parse_float_interval(text.as_bytes()).unwrap();
+            assert_eq!(status.contains(FloatStatus::INEXACT), !interval.is_exact, "inexact of {text}: {status:?}");
+            assert_eq!(status.contains(FloatStatus::OVERFLOW), value.is_infinite(), "overflow of {text}: {status:?}, {value:e}");
+            assert_eq!(status.contains(FloatStatus::SUBNORMAL), value.is_subnormal(), "subnormal of {text}: {status:?}, {value:e}");
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Reader of Unicode text with decimal digits of any script, used by [`ParseOptions::unicode`](crate::ParseOptions::unicode)
+
+use crate::reader::{CodeUnit, Reader};
+
+// Zero of every run of ten decimal digits (general category Nd) of Unicode 17.0
+const DIGIT_ZEROS: [u32; 77] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450,
+    0x114D0, 0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50,
+    0x11D50, 0x11DA0, 0x11DE0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0,
+    0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950,
+    0x1FBF0,
+];
+
+const MINUS_SIGN: char = '\u{2212}';
+
+// Value of decimal digit of any script
+fn digit_value(c: char) -> Option<u8> {
+    let c = c as u32;
+    let zero = match DIGIT_ZEROS.binary_search(&c) {
+        Ok(i) => DIGIT_ZEROS[i],
+        Err(0) => return None,
+        Err(i) => DIGIT_ZEROS[i - 1],
+    };
+    (c - zero < 10).then(|| (c - zero) as u8)
+}
+
+// Byte of the pattern for char C: decimal digits are ASCII digits, minus sign is '-',
+// whitespace is ' ', other chars out of ASCII and invalid code units are 0xFF
+fn to_ascii(c: Option<char>) -> u8 {
+    match c {
+    Some(c) if c.is_ascii() => c as u8,
+    Some(MINUS_SIGN) => b'-',
+    Some(c) => match digit_value(c) {
+        Some(value) => b'0' + value,
+        None if c.is_whitespace() => b' ',
+        None => 0xFF,
+        },
+    None => 0xFF,
+    }
+}
+
+// Reader over chars of text, which are mapped to ASCII, position is in code units
+#[derive(Clone)]
+pub(crate) struct UnicodeReader<'a, T> {
+    text: &'a [T],
+    position: usize,
+    // current char and its length in code units, None for invalid code unit
+    current: Option<char>,
+    len: usize,
+}
+
+impl<'a, T: CodeUnit> UnicodeReader<'a, T> {
+    pub(crate) fn new(text: &'a [T]) -> Self {
+        let mut reader = UnicodeReader{
+            text,
+            position: 0,
+            current: None,
+            len: 0,
+        };
+        reader.decode();
+        reader
+    }
+
+    fn decode(&mut self) {
+        (self.current, self.len) = T::decode(&self.text[self.position.min(self.text.len())..]);
+    }
+}
+
+impl<'a, T: CodeUnit> Reader<'a> for UnicodeReader<'a, T> {
+    type Unit = T;
+
+    fn get(&self) -> u8 {
+        if self.ended() { 0 } else { to_ascii(self.current) }
+    }
+
+    fn get_ascii(&self) -> u8 {
+        self.unit().map_or(0, CodeUnit::to_ascii)
+    }
+
+    fn unit(&self) -> Option<T> {
+        self.text.get(self.position).copied()
+    }
+
+    fn advance(&mut self) {
+        self.position += self.len.max(1);
+        self.decode();
+    }
+
+    fn ended(&self) -> bool {
+        self.position >= self.text.len()
+    }
+
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn skip_digit_separator(&mut self, separator: Option<char>) -> bool {
+        if separator.is_none() || self.ended() || self.current != separator {
+            return false;
+        }
+        let mut p = self.clone();
+        p.advance();
+        if !p.get().is_ascii_digit() {
+            return false;
+        }
+        *self = p;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{digit_value, DIGIT_ZEROS};
+    use crate::{parse_float_exact_with, parse_float_utf16, parse_float_utf32, parse_float_with, Locale, ParseOptions};
+    use crate::random::{random_generator, EDGE_DECIMALS};
+
+    #[test]
+    fn digits() {
+        // digits are numeric chars of std in runs of ten from zero, ASCII digits are the same
+        // as char::to_digit
+        assert!(DIGIT_ZEROS.windows(2).all(|w| w[0] + 10 <= w[1]));
+        for c in (0..=char::MAX as u32).filter_map(char::from_u32) {
+            if let Some(value) = digit_value(c) {
+                assert!(c.is_numeric(), "{:X}", c as u32);
+                assert!(DIGIT_ZEROS.contains(&(c as u32 - value as u32)), "{:X}", c as u32);
+            }
+            if c.is_ascii() {
+                assert_eq!(digit_value(c), c.to_digit(10).map(|d| d as u8), "{:X}", c as u32);
+            }
+        }
+
+        // numeric chars which are not digits (general categories Nl and No) in runs of ten
+        // and more, so digits of a script added to the Unicode of std are not missed
+        const OTHER_NUMBERS: [u32; 33] = [
+            0x0F2A, 0x1369, 0x17F0, 0x2080, 0x2150, 0x2460, 0x24EA, 0x2776, 0x3220, 0x3251, 0x3280,
+            0x32B1, 0xA6E6, 0x10107, 0x10140, 0x102E1, 0x109C0, 0x109D2, 0x10E60, 0x10F1D, 0x11052,
+            0x111E1, 0x11C5A, 0x11FC0, 0x12400, 0x16E80, 0x1D2C0, 0x1D2E0, 0x1D360, 0x1EC71, 0x1ED01,
+            0x1ED2F, 0x1F100,
+        ];
+        let is_other = |c: u32| char::from_u32(c).is_some_and(|c| c.is_numeric() && digit_value(c).is_none());
+        let mut c = 0;
+        while c <= char::MAX as u32 {
+            let first = c;
+            while is_other(c) {
+                c += 1;
+            }
+            assert!(c - first < 10 || OTHER_NUMBERS.contains(&first), "{first:X}");
+            c += 1;
+        }
+
+        for c in ['\u{11F51}', '\u{1E4F1}', '\u{116D1}', '\u{116DB}', '\u{11BF1}', '\u{1E5F2}', '\u{16131}'] {
+            assert_eq!(digit_value(c), Some(1), "{c}");
+        }
+        for c in ['a', '\u{B2}', '\u{2160}', '\u{FF1A}', '\u{2F}', '\u{3A}'] {
+            assert_eq!(digit_value(c), None, "{c}");
+        }
+    }
+
+    #[test]
+    fn unicode_text() {
+        let options = ParseOptions::new().unicode(true);
+        let parse = |text: &str| parse_float_with(text.as_bytes(), &options);
+        assert_eq!(parse("\u{FF11}\u{FF12}\u{FF13}.5"), Some((123.5, 11)));
+        assert_eq!(parse("\u{2212}\u{0661}\u{0662}e\u{0663} x"), Some((-12e3, 10)));
+        assert_eq!(parse("\u{2212}inf"), Some((f64::NEG_INFINITY, 6)));
+        assert_eq!(parse("\u{0967}\u{0968}\u{0969}"), Some((123.0, 9)));
+        assert_eq!(parse("1\u{1D7D0}"), Some((12.0, 5)));
+        assert_eq!(parse("\u{2212}"), None);
+        assert_eq!(parse("\u{FF0E}5"), None);
+        assert_eq!(parse_float_with(b"1\xFF2", &options), Some((1.0, 1)));
+        assert_eq!(parse_float_with(b"1\xE2\x88", &options), Some((1.0, 1)));
+        assert_eq!(parse_float_with("\u{FF11}".as_bytes(), &ParseOptions::new()), None);
+
+        // whitespace out of ASCII and grouping separator
+        let spaces = options.clone().leading_whitespace(true).trailing_whitespace(true);
+        assert_eq!(parse_float_exact_with("\u{A0}\u{2212}\u{FF17}\u{3000}".as_bytes(), &spaces), Ok(-7.0));
+        let ru = options.clone().locale(Locale::RU);
+        assert_eq!(parse_float_exact_with("\u{2212}\u{FF11}\u{A0}\u{FF12}\u{FF13}\u{FF14},\u{FF15}".as_bytes(), &ru), Ok(-1234.5));
+
+        let utf16: Vec<u16> = "\u{2212}\u{104A1}\u{FF12}e1".encode_utf16().collect();
+        assert_eq!(parse_float_utf16(&utf16, &options), Some((-120.0, 6)));
+        assert_eq!(parse_float_utf16(&[0x31, 0xD801, 0x32], &options), Some((1.0, 1)));
+        let utf32: Vec<u32> = "\u{2212}\u{104A1}\u{FF12}e1".chars().map(|c| c as u32).collect();
+        assert_eq!(parse_float_utf32(&utf32, &options), Some((-120.0, 5)));
+        assert_eq!(parse_float_utf32(&[0x31, 0x11_0032], &options), Some((1.0, 1)));
+
+        // hexadecimal floats after the sign and NaN payloads are ASCII only
+        let hex = options.clone().hex_floats(true).nan_payloads(true);
+        let parse = |text: &str| parse_float_with(text.as_bytes(), &hex).map(|(v, n)| (v.to_bits(), n));
+        assert_eq!(parse("\u{2212}0x1.8p1"), Some(((-3.0f64).to_bits(), 10)));
+        assert_eq!(parse("0x\u{FF11}p1"), Some((0, 1)));
+        assert_eq!(parse("0x1p\u{FF11}"), Some((1f64.to_bits(), 3)));
+        assert_eq!(parse("\u{FF10}x1p1"), Some((0, 3)));
+        assert_eq!(parse("nan(1)"), Some((0x7FF8_0000_0000_0001, 6)));
+        assert_eq!(parse("nan(\u{FF11})"), Some((0x7FF8_0000_0000_0000, 3)));
+    }
+
+    #[test]
+    fn same_as_ascii() {
+        let mut texts: Vec<String> = EDGE_DECIMALS.map(String::from).to_vec();
+        const ALPHABET: &[u8] = b"0123456789.eE+-infINFaty x";
+        let mut next = random_generator(0x9B05688C2B3E6C1F);
+        for _ in 0..10_000 {
+            let len = (next() % 24) as usize;
+            texts.push((0..len).map(|_| ALPHABET[(next() % ALPHABET.len() as u64) as usize] as char).collect());
+        }
+
+        let options = ParseOptions::new().unicode(true);
+        for ascii in texts {
+            // digits of random scripts, with offsets of the chars
+            let mut text = String::new();
+            let mut offsets = vec![0];
+            for c in ascii.chars() {
+                let zero = DIGIT_ZEROS[(next() % DIGIT_ZEROS.len() as u64) as usize];
+                text.push(match c {
+                '0'..='9' => char::from_u32(zero + c.to_digit(10).unwrap()).unwrap(),
+                '-' if next().is_multiple_of(2) => '\u{2212}',
+                _ => c,
+                });
+                offsets.push(text.len());
+            }
+
+            let res = parse_float_with(text.as_bytes(), &options);
+            let expected = parse_float_with(ascii.as_bytes(), &ParseOptions::new());
+            assert_eq!(res.is_some(), expected.is_some(), "acceptance of {text:?} as {ascii:?}");
+            if let (Some((value, count)), Some((expected, expected_count))) = (res, expected) {
+                assert_eq!(value.to_bits(), expected.to_bits(), "value of {text:?} as {ascii:?}: {value:e} instead of {expected:e}");
+                assert_eq!(count, offsets[expected_count], "count of {text:?} as {ascii:?}");
+            }
+        }
+    }
+}